@@ -0,0 +1,228 @@
+use crate::flatlanders::{error_relativo_con_angulo, longitud_con_angulo};
+use crate::notacion_angular::NotacionAngular;
+use crate::racional::Racional;
+use std::ops::RangeInclusive;
+
+/// Ángulo del sol sobre el horizonte, medido desde el oeste.
+///
+/// # Variantes
+///
+/// * `Grados` - El ángulo en grados, que puede no ser entero
+/// * `Pendiente` - La pendiente exacta `tan(theta)` del rayo de sol; si es negativa el
+///   sol está al este. Con alturas enteras, las sombras tienen extremos racionales
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::notacion_angular::NotacionAngular;
+///
+/// let theta = AnguloDelSol::leer("30°30'", NotacionAngular::Automatica).unwrap();
+/// assert_eq!(theta, AnguloDelSol::Grados(30.5));
+///
+/// let pendiente = AnguloDelSol::leer("1/1", NotacionAngular::Automatica).unwrap();
+/// assert!((pendiente.grados() - 45.0).abs() < 1e-12);
+/// assert!((pendiente.longitud_de_sombra(10.0) - 10.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnguloDelSol {
+    Grados(f64),
+    Pendiente(Racional),
+}
+
+impl AnguloDelSol {
+    /// Interpreta un ángulo escrito en la notación indicada.
+    ///
+    /// # Argumentos
+    ///
+    /// * `texto` - El ángulo tal como aparece en la entrada
+    /// * `notacion` - La notación del ángulo, o `Automatica` para deducirla del texto
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::NumeroInvalido` - Si el texto no es un ángulo válido en esa notación
+    /// * `ErrorTipo::FueraDeRango` - Si la pendiente es cero (el sol en el horizonte), o
+    ///   los minutos o segundos no están entre 0 y 60
+    pub fn leer(texto: &str, notacion: NotacionAngular) -> Result<Self, crate::ErrorTipo> {
+        let texto = texto.trim();
+        match notacion {
+            NotacionAngular::Automatica => Self::leer(texto, NotacionAngular::deducir(texto)),
+            NotacionAngular::Grados => leer_numero(texto).map(Self::Grados),
+            NotacionAngular::GradosMinutosSegundos => leer_gms(texto).map(Self::Grados),
+            NotacionAngular::Radianes => {
+                let radianes = leer_numero(texto.strip_suffix("rad").unwrap_or(texto))?;
+                Ok(Self::Grados(radianes.to_degrees()))
+            }
+            NotacionAngular::Pendiente => leer_pendiente(texto).map(Self::Pendiente),
+        }
+    }
+
+    /// Retorna el ángulo en grados, entre 0 y 180.
+    pub fn grados(&self) -> f64 {
+        match self {
+            Self::Grados(grados) => *grados,
+            Self::Pendiente(pendiente) => {
+                let grados = pendiente.a_f64().atan().to_degrees();
+                if grados < 0.0 { grados + 180.0 } else { grados }
+            }
+        }
+    }
+
+    /// Indica si el ángulo está dentro del rango de grados enteros dado.
+    pub fn esta_en(&self, rango: &RangeInclusive<u32>) -> bool {
+        (*rango.start() as f64..=*rango.end() as f64).contains(&self.grados())
+    }
+
+    /// Calcula la longitud de la sombra de un poste de altura `h`, negativa si cae hacia el oeste.
+    ///
+    /// Con una pendiente se calcula `h * avance / subida` directamente, sin pasar por grados.
+    pub fn longitud_de_sombra(&self, h: f64) -> f64 {
+        match self {
+            Self::Grados(grados) => longitud_con_angulo(h, *grados),
+            Self::Pendiente(pendiente) => {
+                h * pendiente.denominador() as f64 / pendiente.numerador() as f64
+            }
+        }
+    }
+
+    /// Calcula la longitud exacta de la sombra de un poste de altura `h`, `h / tan(theta)`.
+    ///
+    /// # Retorna
+    ///
+    /// `None` si el ángulo no es una pendiente, porque la longitud no es racional, o si
+    /// la longitud no entra en un `Racional`.
+    pub fn longitud_exacta(&self, h: u32) -> Option<Racional> {
+        match self {
+            Self::Grados(_) => None,
+            Self::Pendiente(pendiente) => Racional::new(
+                (h as i128).checked_mul(pendiente.denominador())?,
+                pendiente.numerador(),
+            ),
+        }
+    }
+
+    /// Retorna una cota del error relativo de `longitud_de_sombra`.
+    ///
+    /// Con una pendiente el error es el de pasar la subida y el avance a `f64` y el de
+    /// las dos operaciones, a lo sumo un ulp cada uno.
+    pub fn error_relativo(&self) -> f64 {
+        match self {
+            Self::Grados(grados) => error_relativo_con_angulo(*grados),
+            Self::Pendiente(_) => 4.0 * f64::EPSILON,
+        }
+    }
+}
+
+/// Interpreta un número decimal finito.
+fn leer_numero(texto: &str) -> Result<f64, crate::ErrorTipo> {
+    match texto.parse::<f64>() {
+        Ok(valor) if valor.is_finite() => Ok(valor),
+        _ => Err(crate::ErrorTipo::NumeroInvalido),
+    }
+}
+
+/// Interpreta grados, minutos y segundos separados por `°`, `'`, `"` o `:`.
+///
+/// Los minutos y los segundos son opcionales. El signo de los grados vale para todo el
+/// ángulo, así que `-10°30'` son -10.5 grados.
+fn leer_gms(texto: &str) -> Result<f64, crate::ErrorTipo> {
+    let partes: Vec<&str> = texto
+        .split(['°', '\'', '"', ':'])
+        .filter(|parte| !parte.is_empty())
+        .collect();
+    if partes.is_empty() || partes.len() > 3 {
+        return Err(crate::ErrorTipo::NumeroInvalido);
+    }
+
+    let mut grados = 0.0;
+    for (i, parte) in partes.iter().enumerate() {
+        let valor = leer_numero(parte)?;
+        if i > 0 && !(0.0..60.0).contains(&valor) {
+            return Err(crate::ErrorTipo::FueraDeRango);
+        }
+        grados += valor.abs() / 60f64.powi(i as i32);
+    }
+    let negativo = partes
+        .first()
+        .is_some_and(|parte| parte.trim_start().starts_with('-'));
+    Ok(if negativo { -grados } else { grados })
+}
+
+/// Interpreta una pendiente `subida/avance` de enteros, o un entero solo como `subida/1`.
+fn leer_pendiente(texto: &str) -> Result<Racional, crate::ErrorTipo> {
+    let (subida, avance) = texto.split_once('/').unwrap_or((texto, "1"));
+    let (Ok(subida), Ok(avance)) = (subida.trim().parse::<i64>(), avance.trim().parse::<i64>())
+    else {
+        return Err(crate::ErrorTipo::NumeroInvalido);
+    };
+
+    // El sol tiene que estar sobre el horizonte: la subida es positiva y el avance no es cero
+    if subida <= 0 || avance == 0 {
+        return Err(crate::ErrorTipo::FueraDeRango);
+    }
+    Racional::new(subida as i128, avance as i128).ok_or(crate::ErrorTipo::FueraDeRango)
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flatlanders::longitud_de_sombra;
+
+    fn leer(texto: &str) -> Result<AnguloDelSol, crate::ErrorTipo> {
+        AnguloDelSol::leer(texto, NotacionAngular::Automatica)
+    }
+
+    #[test]
+    fn test_grados_enteros_coinciden_con_flatlander() {
+        let theta = leer("30").unwrap();
+        assert_eq!(theta, AnguloDelSol::Grados(30.0));
+        assert_eq!(
+            theta.longitud_de_sombra(150.0),
+            longitud_de_sombra(150.0, 30)
+        );
+    }
+
+    #[test]
+    fn test_notaciones_equivalentes() {
+        let esperado = 30.2575;
+        for texto in ["30.2575", "30°15'27\"", "30:15:27"] {
+            assert!((leer(texto).unwrap().grados() - esperado).abs() < 1e-12);
+        }
+
+        let radianes = AnguloDelSol::leer("0.528093", NotacionAngular::Radianes).unwrap();
+        assert!((radianes.grados() - esperado).abs() < 1e-5);
+        assert!((leer("0.528093rad").unwrap().grados() - esperado).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_gms_negativo_aplica_el_signo_a_todo_el_angulo() {
+        for (texto, esperado) in [("-10°30'", -10.5), ("-10:30:36", -10.51), ("-0°30'", -0.5)] {
+            let grados = leer_gms(texto).unwrap();
+            assert!((grados - esperado).abs() < 1e-12, "{texto}: {grados}");
+        }
+        assert_eq!(leer_gms("10:-30"), Err(crate::ErrorTipo::FueraDeRango));
+    }
+
+    #[test]
+    fn test_pendiente_con_sol_al_este() {
+        let theta = leer("1/-1").unwrap();
+        assert!((theta.grados() - 135.0).abs() < 1e-12);
+        assert_eq!(theta.longitud_exacta(10), Some(Racional::entero(-10)));
+    }
+
+    #[test]
+    fn test_angulos_invalidos() {
+        assert_eq!(leer("abc"), Err(crate::ErrorTipo::NumeroInvalido));
+        assert_eq!(leer("30:75"), Err(crate::ErrorTipo::FueraDeRango));
+        assert_eq!(leer("0/3"), Err(crate::ErrorTipo::FueraDeRango));
+        assert_eq!(leer("3/0"), Err(crate::ErrorTipo::FueraDeRango));
+        assert_eq!(leer("1.5/2"), Err(crate::ErrorTipo::NumeroInvalido));
+        assert_eq!(leer("inf"), Err(crate::ErrorTipo::NumeroInvalido));
+    }
+}
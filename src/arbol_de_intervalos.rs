@@ -0,0 +1,202 @@
+use crate::intervalos::Intervalo;
+
+/// Árbol de intervalos para consultar qué sombras cubren un punto o un tramo del camino.
+///
+/// Los intervalos se guardan ordenados por inicio y se recorren como un árbol binario
+/// balanceado implícito: el nodo de cada rango `[desde, hasta)` es su elemento del medio.
+/// Cada nodo guarda además el mayor `fin` de su subárbol, lo que permite descartar ramas
+/// enteras que terminan antes del punto buscado.
+///
+/// Las consultas retornan los índices de los intervalos en el slice original, que para
+/// los intervalos de `crear_intervalo_de_flatlanders` coinciden con el orden de entrada
+/// de los flatlanders.
+///
+/// # Campos
+///
+/// * `intervalos` - Los intervalos ordenados por inicio
+/// * `indices` - El índice original de cada intervalo
+/// * `maximos` - El mayor `fin` del subárbol cuya raíz es cada posición
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::arbol_de_intervalos::ArbolDeIntervalos;
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::{Flatlander, crear_intervalo_de_flatlanders};
+/// use el_rompecabezas_de_las_sombreas::modelo_de_luz::ModeloDeLuz;
+///
+/// let flatlanders = vec![
+///     Flatlander::new(0, 10, 45),  // Sombra de [0, 10]
+///     Flatlander::new(5, 10, 45),  // Sombra de [5, 15]
+///     Flatlander::new(30, 5, 45),  // Sombra de [30, 35]
+/// ];
+///
+/// let sol = ModeloDeLuz::Sol { theta: AnguloDelSol::Grados(45.0) };
+///
+/// let arbol = ArbolDeIntervalos::new(&crear_intervalo_de_flatlanders(&flatlanders, &sol).unwrap());
+/// assert_eq!(arbol.consultar_punto(7.0), vec![0, 1]);
+/// assert!(!arbol.esta_en_sombra(20.0));
+/// ```
+#[derive(Debug)]
+pub struct ArbolDeIntervalos {
+    intervalos: Vec<Intervalo>,
+    indices: Vec<usize>,
+    maximos: Vec<f64>,
+}
+
+impl ArbolDeIntervalos {
+    /// Construye el árbol a partir de una lista de intervalos.
+    ///
+    /// # Argumentos
+    ///
+    /// * `intervalos` - Los intervalos a indexar; su posición en el slice es su índice
+    pub fn new(intervalos: &[Intervalo]) -> Self {
+        let mut indices: Vec<usize> = (0..intervalos.len()).collect();
+        indices.sort_by(|&a, &b| intervalos[a].inicio.total_cmp(&intervalos[b].inicio));
+
+        let mut arbol = Self {
+            intervalos: indices.iter().map(|&i| intervalos[i]).collect(),
+            indices,
+            maximos: vec![f64::NEG_INFINITY; intervalos.len()],
+        };
+        arbol.calcular_maximos(0, intervalos.len());
+        arbol
+    }
+
+    /// Calcula el mayor `fin` del subárbol del rango `[desde, hasta)` y lo retorna.
+    fn calcular_maximos(&mut self, desde: usize, hasta: usize) -> f64 {
+        if desde >= hasta {
+            return f64::NEG_INFINITY;
+        }
+
+        let medio = (desde + hasta) / 2;
+        let izquierdo = self.calcular_maximos(desde, medio);
+        let derecho = self.calcular_maximos(medio + 1, hasta);
+        self.maximos[medio] = self.intervalos[medio].fin.max(izquierdo).max(derecho);
+        self.maximos[medio]
+    }
+
+    /// Retorna los índices de los intervalos que contienen el punto `x`, en orden creciente.
+    pub fn consultar_punto(&self, x: f64) -> Vec<usize> {
+        self.consultar_rango(&Intervalo { inicio: x, fin: x })
+    }
+
+    /// Retorna los índices de los intervalos que se superponen con `rango`, en orden creciente.
+    ///
+    /// Los intervalos que solo tocan un extremo del rango también se incluyen.
+    pub fn consultar_rango(&self, rango: &Intervalo) -> Vec<usize> {
+        let mut encontrados = Vec::new();
+        self.buscar(0, self.intervalos.len(), rango, &mut encontrados);
+        encontrados.sort_unstable();
+        encontrados
+    }
+
+    /// Indica si el punto `x` está cubierto por al menos una sombra.
+    pub fn esta_en_sombra(&self, x: f64) -> bool {
+        !self.consultar_punto(x).is_empty()
+    }
+
+    /// Recorre el subárbol del rango `[desde, hasta)` agregando los intervalos que se
+    /// superponen con `rango`.
+    fn buscar(&self, desde: usize, hasta: usize, rango: &Intervalo, encontrados: &mut Vec<usize>) {
+        if desde >= hasta {
+            return;
+        }
+
+        let medio = (desde + hasta) / 2;
+        // Si todo el subárbol termina antes del rango, no hay nada que buscar
+        if self.maximos[medio] < rango.inicio {
+            return;
+        }
+
+        self.buscar(desde, medio, rango, encontrados);
+
+        // Los intervalos a la derecha empiezan todavía más tarde que el del medio
+        if self.intervalos[medio].inicio > rango.fin {
+            return;
+        }
+        if self.intervalos[medio].fin >= rango.inicio {
+            encontrados.push(self.indices[medio]);
+        }
+
+        self.buscar(medio + 1, hasta, rango, encontrados);
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arbol_de(pares: &[(f64, f64)]) -> ArbolDeIntervalos {
+        let intervalos: Vec<Intervalo> = pares
+            .iter()
+            .map(|&(inicio, fin)| Intervalo { inicio, fin })
+            .collect();
+        ArbolDeIntervalos::new(&intervalos)
+    }
+
+    #[test]
+    fn test_arbol_vacio() {
+        let arbol = arbol_de(&[]);
+        assert!(arbol.consultar_punto(0.0).is_empty());
+        assert!(!arbol.esta_en_sombra(0.0));
+    }
+
+    #[test]
+    fn test_consultar_punto() {
+        let arbol = arbol_de(&[(20.0, 30.0), (0.0, 10.0), (5.0, 25.0), (40.0, 41.0)]);
+
+        assert_eq!(arbol.consultar_punto(7.0), vec![1, 2]);
+        assert_eq!(arbol.consultar_punto(22.0), vec![0, 2]);
+        assert_eq!(arbol.consultar_punto(35.0), Vec::<usize>::new());
+        assert_eq!(arbol.consultar_punto(41.0), vec![3]);
+    }
+
+    #[test]
+    fn test_consultar_punto_en_extremos() {
+        let arbol = arbol_de(&[(0.0, 5.0), (5.0, 10.0)]);
+        assert_eq!(arbol.consultar_punto(5.0), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_consultar_rango() {
+        let arbol = arbol_de(&[(0.0, 5.0), (10.0, 15.0), (20.0, 25.0), (30.0, 35.0)]);
+
+        let rango = Intervalo {
+            inicio: 12.0,
+            fin: 21.0,
+        };
+        assert_eq!(arbol.consultar_rango(&rango), vec![1, 2]);
+
+        let hueco = Intervalo {
+            inicio: 26.0,
+            fin: 29.0,
+        };
+        assert!(arbol.consultar_rango(&hueco).is_empty());
+    }
+
+    #[test]
+    fn test_consultas_coinciden_con_busqueda_lineal() {
+        let pares: Vec<(f64, f64)> = (0..50)
+            .map(|i| {
+                let inicio = ((i * 37) % 101) as f64;
+                (inicio, inicio + ((i * 13) % 17) as f64)
+            })
+            .collect();
+        let arbol = arbol_de(&pares);
+
+        for x in 0..120 {
+            let x = x as f64;
+            let esperado: Vec<usize> = (0..pares.len())
+                .filter(|&i| pares[i].0 <= x && x <= pares[i].1)
+                .collect();
+            assert_eq!(arbol.consultar_punto(x), esperado);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::flatlanders::Flatlander;
+use std::collections::HashMap;
+
+/// Árbol de segmentos sobre los extremos comprimidos de las sombras de una escena.
+///
+/// Permite agregar y quitar la sombra de un flatlander en tiempo logarítmico y conocer
+/// la longitud total cubierta después de cada operación. Las coordenadas se comprimen
+/// a partir de los flatlanders que pueden llegar a aparecer en la escena, por lo que
+/// solo se aceptan sombras de ese universo.
+///
+/// # Campos
+///
+/// * `coordenadas` - Los extremos de todas las sombras posibles, ordenados y sin repetir
+/// * `cuenta` - Cantidad de sombras que cubren por completo el rango de cada nodo
+/// * `cubierto` - Longitud cubierta dentro del rango de cada nodo
+/// * `activas` - Cantidad de sombras activas por par de extremos comprimidos
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::arbol_de_sombras::ArbolDeSombras;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+///
+/// let escena = vec![Flatlander::new(0, 10, 45), Flatlander::new(5, 10, 45)];
+/// let mut arbol = ArbolDeSombras::new(&escena);
+///
+/// assert!((arbol.agregar(&escena[0]).unwrap() - 10.0).abs() < 1e-9);
+/// assert!((arbol.agregar(&escena[1]).unwrap() - 15.0).abs() < 1e-9);
+/// assert!((arbol.quitar(&escena[0]).unwrap() - 10.0).abs() < 1e-9);
+/// ```
+#[derive(Debug)]
+pub struct ArbolDeSombras {
+    coordenadas: Vec<f64>,
+    cuenta: Vec<u32>,
+    cubierto: Vec<f64>,
+    activas: HashMap<(usize, usize), u32>,
+}
+
+impl ArbolDeSombras {
+    /// Crea un árbol vacío preparado para las sombras de los flatlanders dados.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Todos los flatlanders que pueden agregarse o quitarse después
+    pub fn new(flatlanders: &[Flatlander]) -> Self {
+        let mut coordenadas: Vec<f64> = flatlanders
+            .iter()
+            .flat_map(|f| {
+                let sombra = f.sombra();
+                [sombra.inicio, sombra.fin]
+            })
+            .collect();
+        coordenadas.sort_by(|a, b| a.total_cmp(b));
+        coordenadas.dedup();
+
+        let nodos = 4 * coordenadas.len().max(1);
+        Self {
+            coordenadas,
+            cuenta: vec![0; nodos],
+            cubierto: vec![0.0; nodos],
+            activas: HashMap::new(),
+        }
+    }
+
+    /// Agrega la sombra de un flatlander y retorna la nueva longitud cubierta.
+    ///
+    /// # Retorna
+    ///
+    /// * `Some(f64)` - La longitud cubierta luego de agregar la sombra
+    /// * `None` - Si la sombra no pertenece a los flatlanders con los que se creó el árbol
+    pub fn agregar(&mut self, flatlander: &Flatlander) -> Option<f64> {
+        let extremos = self.comprimir(flatlander)?;
+        *self.activas.entry(extremos).or_insert(0) += 1;
+        self.actualizar_raiz(extremos, true);
+        Some(self.longitud_cubierta())
+    }
+
+    /// Quita la sombra de un flatlander y retorna la nueva longitud cubierta.
+    ///
+    /// # Retorna
+    ///
+    /// * `Some(f64)` - La longitud cubierta luego de quitar la sombra
+    /// * `None` - Si no hay ninguna sombra activa con esos extremos
+    pub fn quitar(&mut self, flatlander: &Flatlander) -> Option<f64> {
+        let extremos = self.comprimir(flatlander)?;
+        let activas = self.activas.get_mut(&extremos).filter(|c| **c > 0)?;
+        *activas -= 1;
+        self.actualizar_raiz(extremos, false);
+        Some(self.longitud_cubierta())
+    }
+
+    /// Retorna la longitud cubierta por las sombras activas.
+    pub fn longitud_cubierta(&self) -> f64 {
+        self.cubierto[1]
+    }
+
+    /// Traduce los extremos de la sombra de un flatlander a índices de `coordenadas`.
+    fn comprimir(&self, flatlander: &Flatlander) -> Option<(usize, usize)> {
+        let sombra = flatlander.sombra();
+        let buscar = |valor: f64| {
+            self.coordenadas
+                .binary_search_by(|c| c.total_cmp(&valor))
+                .ok()
+        };
+        Some((buscar(sombra.inicio)?, buscar(sombra.fin)?))
+    }
+
+    /// Aplica una actualización desde la raíz, si hay al menos un segmento elemental.
+    fn actualizar_raiz(&mut self, (izq, der): (usize, usize), sumar: bool) {
+        let segmentos = self.coordenadas.len().saturating_sub(1);
+        if izq < der {
+            self.actualizar(1, 0, segmentos, izq, der, sumar);
+        }
+    }
+
+    /// Suma o resta una sombra sobre los segmentos elementales `[izq, der)`.
+    ///
+    /// El nodo `nodo` representa los segmentos elementales `[desde, hasta)`.
+    fn actualizar(
+        &mut self,
+        nodo: usize,
+        desde: usize,
+        hasta: usize,
+        izq: usize,
+        der: usize,
+        sumar: bool,
+    ) {
+        if der <= desde || hasta <= izq {
+            return;
+        }
+
+        if izq <= desde && hasta <= der {
+            if sumar {
+                self.cuenta[nodo] += 1;
+            } else {
+                self.cuenta[nodo] -= 1;
+            }
+        } else {
+            let medio = (desde + hasta) / 2;
+            self.actualizar(2 * nodo, desde, medio, izq, der, sumar);
+            self.actualizar(2 * nodo + 1, medio, hasta, izq, der, sumar);
+        }
+        self.recalcular(nodo, desde, hasta);
+    }
+
+    /// Recalcula la longitud cubierta de un nodo a partir de su cuenta y de sus hijos.
+    fn recalcular(&mut self, nodo: usize, desde: usize, hasta: usize) {
+        self.cubierto[nodo] = if self.cuenta[nodo] > 0 {
+            self.coordenadas[hasta] - self.coordenadas[desde]
+        } else if hasta - desde == 1 {
+            0.0
+        } else {
+            self.cubierto[2 * nodo] + self.cubierto[2 * nodo + 1]
+        };
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+
+    fn assert_longitud(actual: Option<f64>, esperado: f64) {
+        let actual = actual.expect("Se esperaba una longitud");
+        assert!((actual - esperado).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    fn escena() -> Vec<Flatlander> {
+        vec![
+            Flatlander::new(0, 10, 45),
+            Flatlander::new(5, 10, 45),
+            Flatlander::new(20, 5, 45),
+        ]
+    }
+
+    #[test]
+    fn test_arbol_vacio() {
+        let arbol = ArbolDeSombras::new(&escena());
+        assert_eq!(arbol.longitud_cubierta(), 0.0);
+
+        let arbol_sin_flatlanders = ArbolDeSombras::new(&[]);
+        assert_eq!(arbol_sin_flatlanders.longitud_cubierta(), 0.0);
+    }
+
+    #[test]
+    fn test_agregar_y_quitar_sombras() {
+        let escena = escena();
+        let mut arbol = ArbolDeSombras::new(&escena);
+
+        assert_longitud(arbol.agregar(&escena[0]), 10.0);
+        assert_longitud(arbol.agregar(&escena[1]), 15.0);
+        assert_longitud(arbol.agregar(&escena[2]), 20.0);
+        assert_longitud(arbol.quitar(&escena[0]), 15.0);
+        assert_longitud(arbol.quitar(&escena[1]), 5.0);
+        assert_longitud(arbol.quitar(&escena[2]), 0.0);
+    }
+
+    #[test]
+    fn test_sombras_repetidas() {
+        let escena = escena();
+        let mut arbol = ArbolDeSombras::new(&escena);
+
+        assert_longitud(arbol.agregar(&escena[0]), 10.0);
+        assert_longitud(arbol.agregar(&escena[0]), 10.0);
+        assert_longitud(arbol.quitar(&escena[0]), 10.0);
+        assert_longitud(arbol.quitar(&escena[0]), 0.0);
+    }
+
+    #[test]
+    fn test_quitar_sombra_no_activa() {
+        let escena = escena();
+        let mut arbol = ArbolDeSombras::new(&escena);
+
+        assert_eq!(arbol.quitar(&escena[0]), None);
+        assert_longitud(arbol.agregar(&escena[1]), 10.0);
+        assert_eq!(arbol.quitar(&escena[0]), None);
+    }
+
+    #[test]
+    fn test_flatlander_fuera_del_universo() {
+        let mut arbol = ArbolDeSombras::new(&escena());
+        assert_eq!(arbol.agregar(&Flatlander::new(100, 3, 45)), None);
+    }
+}
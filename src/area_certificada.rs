@@ -0,0 +1,87 @@
+/// Error absoluto o relativo máximo aceptado para el resultado, según el enunciado.
+pub const TOLERANCIA_DEL_RESULTADO: f64 = 1e-4;
+
+/// Área cubierta junto con un intervalo que garantiza contener el valor exacto.
+///
+/// # Campos
+///
+/// * `area` - La mejor estimación del área, calculada con suma compensada
+/// * `cota_inferior` - Un valor que es seguro menor o igual al área exacta
+/// * `cota_superior` - Un valor que es seguro mayor o igual al área exacta
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::area_certificada::AreaCertificada;
+///
+/// let certificada = AreaCertificada {
+///     area: 300.0,
+///     cota_inferior: 299.99999,
+///     cota_superior: 300.00002,
+/// };
+/// assert!((certificada.error_maximo() - 0.00002).abs() < 1e-9);
+/// assert!(certificada.es_confiable(1e-4));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct AreaCertificada {
+    pub area: f64,
+    pub cota_inferior: f64,
+    pub cota_superior: f64,
+}
+
+impl AreaCertificada {
+    /// Retorna la mayor distancia posible entre `area` y el área exacta.
+    pub fn error_maximo(&self) -> f64 {
+        (self.area - self.cota_inferior).max(self.cota_superior - self.area)
+    }
+
+    /// Indica si el error máximo no supera la tolerancia, en términos absolutos o relativos.
+    ///
+    /// # Argumentos
+    ///
+    /// * `tolerancia` - El error absoluto o relativo máximo aceptado
+    pub fn es_confiable(&self, tolerancia: f64) -> bool {
+        self.error_maximo() <= tolerancia * self.area.abs().max(1.0)
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_maximo_asimetrico() {
+        let certificada = AreaCertificada {
+            area: 10.0,
+            cota_inferior: 9.0,
+            cota_superior: 10.5,
+        };
+        assert_eq!(certificada.error_maximo(), 1.0);
+    }
+
+    #[test]
+    fn test_es_confiable_relativo_para_areas_grandes() {
+        let certificada = AreaCertificada {
+            area: 1e6,
+            cota_inferior: 1e6 - 50.0,
+            cota_superior: 1e6 + 50.0,
+        };
+        assert!(certificada.es_confiable(TOLERANCIA_DEL_RESULTADO));
+    }
+
+    #[test]
+    fn test_no_es_confiable() {
+        let certificada = AreaCertificada {
+            area: 1.0,
+            cota_inferior: 0.5,
+            cota_superior: 1.0,
+        };
+        assert!(!certificada.es_confiable(TOLERANCIA_DEL_RESULTADO));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{Intervalo, calcular_area};
+
+/// Radio angular aproximado del sol visto desde la Tierra, en grados.
+pub const RADIO_ANGULAR_DEL_SOL: f64 = 0.27;
+
+/// Longitudes de umbra y penumbra a lo largo del camino con un sol de tamaño finito.
+///
+/// Un punto cuenta como umbra si está en la umbra de algún flatlander, aunque a la vez
+/// esté en la penumbra de otros, y como penumbra si está en la sombra de algún
+/// flatlander pero en la umbra de ninguno.
+///
+/// # Campos
+///
+/// * `umbra` - La longitud del camino a la que no le llega nada del disco solar
+/// * `penumbra` - La longitud del camino a la que le llega solo parte del disco solar
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::area_con_penumbra::AreaConPenumbra;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+///
+/// let flatlanders = vec![Flatlander::new(0, 10, 45), Flatlander::new(5, 10, 45)];
+/// let area = AreaConPenumbra::new(&flatlanders, &AnguloDelSol::Grados(45.0), 0.27);
+///
+/// // La penumbra de la primera sombra queda dentro de la umbra de la segunda
+/// assert!(area.umbra < 15.0 && 15.0 < area.total());
+/// assert!(area.penumbra < 1.0);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct AreaConPenumbra {
+    pub umbra: f64,
+    pub penumbra: f64,
+}
+
+impl AreaConPenumbra {
+    /// Calcula la umbra y la penumbra de los flatlanders con un sol de radio angular dado.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders de la escena
+    /// * `theta` - El ángulo del centro del sol
+    /// * `radio_angular` - El radio angular del sol en grados, ver `RADIO_ANGULAR_DEL_SOL`
+    pub fn new(flatlanders: &[Flatlander], theta: &AnguloDelSol, radio_angular: f64) -> Self {
+        let (umbras, mut completas): (Vec<Option<Intervalo>>, Vec<Intervalo>) = flatlanders
+            .iter()
+            .map(|f| f.sombras_con_disco_solar(theta, radio_angular))
+            .unzip();
+        let mut umbras: Vec<Intervalo> = umbras.into_iter().flatten().collect();
+
+        // Cada umbra está dentro de su sombra completa, así que la unión de las umbras
+        // también está dentro de la unión de las sombras completas
+        let umbra = calcular_area(&mut umbras);
+        Self {
+            umbra,
+            penumbra: calcular_area(&mut completas) - umbra,
+        }
+    }
+
+    /// Retorna la longitud total en sombra, sumando umbra y penumbra.
+    pub fn total(&self) -> f64 {
+        self.umbra + self.penumbra
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::angulo_del_sol::AnguloDelSol;
+    use crate::flatlanders::crear_intervalo_de_flatlanders;
+    use crate::modelo_de_luz::ModeloDeLuz;
+
+    #[test]
+    fn test_sol_puntual_no_tiene_penumbra() {
+        let flatlanders = vec![
+            Flatlander::new(50, 150, 30),
+            Flatlander::new(0, 100, 30),
+            Flatlander::new(100, 200, 30),
+        ];
+        let sol_a_30 = AnguloDelSol::Grados(30.0);
+        let sol = ModeloDeLuz::Sol { theta: sol_a_30 };
+        let area = calcular_area(&mut crear_intervalo_de_flatlanders(&flatlanders, &sol).unwrap());
+
+        let con_penumbra = AreaConPenumbra::new(&flatlanders, &sol_a_30, 0.0);
+        assert!((con_penumbra.umbra - area).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!(con_penumbra.penumbra.abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_umbra_dentro_de_la_penumbra_de_otro() {
+        // Bordes del sol a 40° y 50°: el alto proyecta umbra hasta 100 / tan(50°) y
+        // penumbra hasta 100 / tan(40°), que tapa por completo al bajo en x = 90
+        let flatlanders = vec![Flatlander::new(0, 100, 45), Flatlander::new(90, 5, 45)];
+        let area = AreaConPenumbra::new(&flatlanders, &AnguloDelSol::Grados(45.0), 5.0);
+
+        let umbra_alto = 100.0 / 50.0_f64.to_radians().tan();
+        let hasta = 100.0 / 40.0_f64.to_radians().tan();
+        let umbra_bajo = 5.0 / 50.0_f64.to_radians().tan();
+
+        assert!((area.umbra - (umbra_alto + umbra_bajo)).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((area.total() - hasta).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_flatlander_que_flota() {
+        // Flota entre 10 y 20 con el sol a 45°: toda la sombra queda dentro de [10, 20]
+        // salvo lo que estiran los bordes del disco
+        let flatlanders = vec![Flatlander::new(0, 20, 45).con_elevacion(10).unwrap()];
+        let area = AreaConPenumbra::new(&flatlanders, &AnguloDelSol::Grados(45.0), 0.27);
+
+        assert!(area.umbra < 10.0);
+        assert!((area.total() - 10.0).abs() < 0.5);
+    }
+}
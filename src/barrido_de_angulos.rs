@@ -0,0 +1,162 @@
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{Intervalo, fusionar_ordenados, longitud_total};
+use std::ops::RangeInclusive;
+
+/// Calcula la longitud en sombra de una misma escena para muchos ángulos del sol.
+///
+/// Ordena los flatlanders por posición una sola vez. Con el sol al oeste las sombras de
+/// los flatlanders apoyados en el camino empiezan en su posición, así que el orden por
+/// inicio de las sombras es siempre ese y cada ángulo se resuelve en una sola pasada. Si
+/// para algún ángulo las sombras no quedan en ese orden (sol al este, flatlanders que
+/// flotan o siluetas), se ordenan solo para ese ángulo.
+///
+/// # Campos
+///
+/// * `flatlanders` - Los flatlanders de la escena
+/// * `orden` - Los índices de los flatlanders ordenados por posición
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::barrido_de_angulos::BarridoDeAngulos;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+///
+/// let flatlanders = vec![Flatlander::new(5, 10, 45), Flatlander::new(0, 10, 45)];
+/// let barrido = BarridoDeAngulos::new(&flatlanders);
+///
+/// let tabla = barrido.barrer(&(45.0..=90.0), 45.0).unwrap();
+/// assert_eq!(tabla.len(), 2);
+/// assert!((tabla[0].1 - 15.0).abs() < 1e-9);
+/// assert_eq!(tabla[1], (90.0, 0.0));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct BarridoDeAngulos<'a> {
+    flatlanders: &'a [Flatlander],
+    orden: Vec<usize>,
+}
+
+impl<'a> BarridoDeAngulos<'a> {
+    /// Prepara el barrido ordenando los flatlanders por posición.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders de la escena; el ángulo con el que fueron creados no se usa
+    pub fn new(flatlanders: &'a [Flatlander]) -> Self {
+        let mut orden: Vec<usize> = (0..flatlanders.len()).collect();
+        orden.sort_by_key(|&i| flatlanders[i].x);
+        Self { flatlanders, orden }
+    }
+
+    /// Calcula la longitud en sombra con el sol a `grados` grados.
+    ///
+    /// # Argumentos
+    ///
+    /// * `grados` - El ángulo de incidencia de la luz en grados, puede no ser entero
+    pub fn longitud_con_angulo(&self, grados: f64) -> f64 {
+        let mut sombras: Vec<Intervalo> = self
+            .orden
+            .iter()
+            .map(|&i| self.flatlanders[i].sombra_con_angulo(grados))
+            .collect();
+
+        if !sombras.is_sorted_by(|a, b| a.inicio <= b.inicio) {
+            sombras.sort_by(|a, b| a.inicio.total_cmp(&b.inicio));
+        }
+        longitud_total(&fusionar_ordenados(&sombras))
+    }
+
+    /// Calcula la longitud en sombra para cada ángulo del rango, avanzando de a `paso` grados.
+    ///
+    /// # Argumentos
+    ///
+    /// * `rango` - El primer y el último ángulo en grados; el último se incluye si el
+    ///   paso cae justo en él
+    /// * `paso` - La distancia en grados entre dos ángulos consecutivos
+    ///
+    /// # Retorna
+    ///
+    /// Una tabla de pares `(ángulo, longitud en sombra)`, ordenada por ángulo.
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::ArgumentoInvalido` - Si el paso no es positivo y finito, o el rango
+    ///   está vacío o no es finito
+    pub fn barrer(
+        &self,
+        rango: &RangeInclusive<f64>,
+        paso: f64,
+    ) -> Result<Vec<(f64, f64)>, crate::ErrorTipo> {
+        let (desde, hasta) = (*rango.start(), *rango.end());
+        if !(paso > 0.0 && paso.is_finite() && desde.is_finite() && hasta.is_finite()) {
+            return Err(crate::ErrorTipo::ArgumentoInvalido);
+        }
+        if desde > hasta {
+            return Err(crate::ErrorTipo::ArgumentoInvalido);
+        }
+
+        // Se cuentan los pasos con un margen para no perder el último por redondeo
+        let pasos = ((hasta - desde) / paso + crate::PRECISION_IGUALDAD_FLOAT).floor() as usize;
+        Ok((0..=pasos)
+            .map(|i| desde + i as f64 * paso)
+            .map(|grados| (grados, self.longitud_con_angulo(grados)))
+            .collect())
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intervalos::calcular_area;
+
+    fn escena() -> Vec<Flatlander> {
+        vec![
+            Flatlander::new(50, 150, 45),
+            Flatlander::new(0, 100, 45),
+            Flatlander::new(100, 200, 45).con_ancho(20),
+            Flatlander::new(60, 90, 45).con_elevacion(40).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_coincide_con_calcular_area_en_cada_angulo() {
+        let flatlanders = escena();
+        let barrido = BarridoDeAngulos::new(&flatlanders);
+
+        for (grados, longitud) in barrido.barrer(&(1.0..=179.0), 2.5).unwrap() {
+            let mut sombras: Vec<Intervalo> = flatlanders
+                .iter()
+                .map(|f| f.sombra_con_angulo(grados))
+                .collect();
+            assert!((longitud - calcular_area(&mut sombras)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_incluye_el_ultimo_angulo() {
+        let flatlanders = escena();
+        let barrido = BarridoDeAngulos::new(&flatlanders);
+
+        let tabla = barrido.barrer(&(10.0..=80.0), 1.0).unwrap();
+        assert_eq!(tabla.len(), 71);
+        assert_eq!(tabla[70].0, 80.0);
+
+        let tabla = barrido.barrer(&(10.0..=11.0), 0.1).unwrap();
+        assert_eq!(tabla.len(), 11);
+    }
+
+    #[test]
+    fn test_argumentos_invalidos() {
+        let flatlanders = escena();
+        let barrido = BarridoDeAngulos::new(&flatlanders);
+
+        assert!(barrido.barrer(&(10.0..=80.0), 0.0).is_err());
+        assert!(barrido.barrer(&(10.0..=80.0), f64::NAN).is_err());
+        assert!(barrido.barrer(&(80.0..=10.0), 1.0).is_err());
+    }
+}
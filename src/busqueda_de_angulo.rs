@@ -0,0 +1,302 @@
+use crate::PRECISION_IGUALDAD_FLOAT;
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{Intervalo, calcular_area, fusionar_intervalos, intersecar_intervalos};
+use crate::limites::Limites;
+use std::ops::RangeInclusive;
+
+/// Cantidad máxima de pasos de bisección, suficiente para agotar la precisión de un `f64`
+const MAXIMO_DE_PASOS: usize = 200;
+
+/// Cantidad de tramos en los que se muestrea el rango antes de bisecar
+const MUESTRAS: usize = 1000;
+
+/// Resultado de buscar el ángulo del sol que produce una longitud en sombra dada.
+///
+/// # Variantes
+///
+/// * `Encontrado` - El ángulo en grados con el que se alcanza la longitud buscada
+/// * `FueraDeAlcance` - Ningún ángulo del rango la alcanza; `minima` y `maxima` son la
+///   menor y la mayor longitud en sombra entre los ángulos muestreados del rango
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::busqueda_de_angulo::{AnguloBuscado, angulo_para_longitud};
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+///
+/// let flatlanders = vec![Flatlander::new(0, 10, 45)];
+///
+/// let angulo = angulo_para_longitud(&flatlanders, 10.0, &(10.0..=80.0)).unwrap();
+/// assert!(matches!(angulo, AnguloBuscado::Encontrado(theta) if (theta - 45.0).abs() < 1e-6));
+///
+/// let angulo = angulo_para_longitud(&flatlanders, 1000.0, &(10.0..=80.0)).unwrap();
+/// assert!(matches!(angulo, AnguloBuscado::FueraDeAlcance { .. }));
+/// ```
+#[derive(Debug, PartialEq)]
+pub enum AnguloBuscado {
+    Encontrado(f64),
+    FueraDeAlcance { minima: f64, maxima: f64 },
+}
+
+/// Busca el ángulo del sol con el que la longitud total en sombra es `objetivo`.
+///
+/// Con el sol al oeste, cuanto más bajo está más se alargan las sombras, así que la
+/// longitud en sombra de flatlanders apoyados en el camino decrece con el ángulo. Si
+/// algún flatlander flota deja de ser monótona, así que el rango se muestrea para
+/// encontrar un tramo donde la longitud cruce el objetivo y se biseca dentro de él.
+///
+/// # Argumentos
+///
+/// * `flatlanders` - Los flatlanders de la escena; el ángulo con el que fueron creados no se usa
+/// * `objetivo` - La longitud en sombra buscada
+/// * `rango` - Los ángulos en grados entre los que se busca, dentro de `(0, 90]`
+///
+/// # Errores
+///
+/// * `ErrorTipo::ArgumentoInvalido` - Si el rango está vacío o no está dentro de `(0, 90]`
+pub fn angulo_para_longitud(
+    flatlanders: &[Flatlander],
+    objetivo: f64,
+    rango: &RangeInclusive<f64>,
+) -> Result<AnguloBuscado, crate::ErrorTipo> {
+    let medir = |grados: f64| {
+        let mut sombras: Vec<Intervalo> = flatlanders
+            .iter()
+            .map(|f| f.sombra_con_angulo(grados))
+            .collect();
+        calcular_area(&mut sombras)
+    };
+    buscar_angulo(medir, objetivo, rango)
+}
+
+/// Busca el ángulo del sol con el que queda en sombra la fracción `fraccion` de la ventana.
+///
+/// Funciona igual que `angulo_para_longitud`, pero midiendo solo la sombra que cae dentro
+/// de la ventana.
+///
+/// # Argumentos
+///
+/// * `flatlanders` - Los flatlanders de la escena
+/// * `ventana` - El tramo del camino que interesa sombrear
+/// * `fraccion` - La fracción de la ventana que debe quedar en sombra, entre 0 y 1
+/// * `rango` - Los ángulos en grados entre los que se busca, dentro de `(0, 90]`
+///
+/// # Errores
+///
+/// * `ErrorTipo::ArgumentoInvalido` - Si el rango no es válido, o la fracción no está entre 0 y 1
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::busqueda_de_angulo::{AnguloBuscado, angulo_para_fraccion};
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+///
+/// // Para sombrear la mitad de [0, 20] la sombra del poste tiene que medir 10
+/// let flatlanders = vec![Flatlander::new(0, 10, 45)];
+/// let ventana = Intervalo { inicio: 0.0, fin: 20.0 };
+///
+/// let angulo = angulo_para_fraccion(&flatlanders, &ventana, 0.5, &(10.0..=80.0)).unwrap();
+/// assert!(matches!(angulo, AnguloBuscado::Encontrado(theta) if (theta - 45.0).abs() < 1e-6));
+/// ```
+pub fn angulo_para_fraccion(
+    flatlanders: &[Flatlander],
+    ventana: &Intervalo,
+    fraccion: f64,
+    rango: &RangeInclusive<f64>,
+) -> Result<AnguloBuscado, crate::ErrorTipo> {
+    if !(0.0..=1.0).contains(&fraccion) {
+        return Err(crate::ErrorTipo::ArgumentoInvalido);
+    }
+
+    let medir = |grados: f64| {
+        let mut sombras: Vec<Intervalo> = flatlanders
+            .iter()
+            .map(|f| f.sombra_con_angulo(grados))
+            .collect();
+        let cubiertas = fusionar_intervalos(&mut sombras);
+        let mut dentro = intersecar_intervalos(&cubiertas, &[*ventana]);
+        calcular_area(&mut dentro)
+    };
+    buscar_angulo(medir, fraccion * (ventana.fin - ventana.inicio), rango)
+}
+
+/// Retorna el rango de ángulos en el que se busca, el de `theta` en los límites pero
+/// solo con el sol al oeste, hasta la vertical.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::busqueda_de_angulo::rango_de_busqueda;
+/// use el_rompecabezas_de_las_sombreas::limites::Limites;
+///
+/// assert_eq!(rango_de_busqueda(&Limites::concurso()), 10.0..=80.0);
+/// assert_eq!(rango_de_busqueda(&Limites::cielo_completo()), 1.0..=90.0);
+/// ```
+pub fn rango_de_busqueda(limites: &Limites) -> RangeInclusive<f64> {
+    let fin = (*limites.theta.end()).min(90);
+    *limites.theta.start() as f64..=fin as f64
+}
+
+/// Busca por bisección un ángulo del rango en el que `medir` vale `objetivo`.
+///
+/// Primero mide en `MUESTRAS + 1` ángulos equiespaciados para acotar la mínima y la
+/// máxima, que con flatlanders flotantes pueden estar en el interior del rango, y toma
+/// el primer tramo entre muestras vecinas cuyas medidas encierran al objetivo. Se
+/// detiene cuando la medida está a menos de `PRECISION_IGUALDAD_FLOAT` (relativa al
+/// objetivo) o cuando el tramo ya no se puede achicar.
+fn buscar_angulo(
+    medir: impl Fn(f64) -> f64,
+    objetivo: f64,
+    rango: &RangeInclusive<f64>,
+) -> Result<AnguloBuscado, crate::ErrorTipo> {
+    let (inicio, fin) = (*rango.start(), *rango.end());
+    if !(inicio > 0.0 && inicio <= fin && fin <= 90.0) {
+        return Err(crate::ErrorTipo::ArgumentoInvalido);
+    }
+
+    let muestras: Vec<(f64, f64)> = (0..=MUESTRAS)
+        .map(|i| {
+            let grados = inicio + (fin - inicio) * i as f64 / MUESTRAS as f64;
+            (grados, medir(grados))
+        })
+        .collect();
+    let (minima, maxima) = muestras.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(minima, maxima), &(_, medida)| (minima.min(medida), maxima.max(medida)),
+    );
+
+    let cruce = muestras.windows(2).find_map(|par| match par {
+        [(bajo, en_bajo), (alto, en_alto)]
+            if en_bajo.min(*en_alto) <= objetivo && objetivo <= en_bajo.max(*en_alto) =>
+        {
+            Some((*bajo, *alto, *en_bajo))
+        }
+        _ => None,
+    });
+    let Some((mut bajo, mut alto, en_bajo)) = cruce else {
+        return Ok(AnguloBuscado::FueraDeAlcance { minima, maxima });
+    };
+
+    // Se mantiene que el objetivo queda entre la medida en `bajo` y la medida en `alto`
+    let tolerancia = PRECISION_IGUALDAD_FLOAT * objetivo.abs().max(1.0);
+    for _ in 0..MAXIMO_DE_PASOS {
+        let medio = (bajo + alto) / 2.0;
+        let en_medio = medir(medio);
+        if (en_medio - objetivo).abs() <= tolerancia || medio == bajo || medio == alto {
+            return Ok(AnguloBuscado::Encontrado(medio));
+        }
+        if (en_medio > objetivo) == (en_bajo > objetivo) {
+            bajo = medio;
+        } else {
+            alto = medio;
+        }
+    }
+
+    Ok(AnguloBuscado::Encontrado((bajo + alto) / 2.0))
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn angulo_encontrado(buscado: Result<AnguloBuscado, crate::ErrorTipo>) -> f64 {
+        match buscado {
+            Ok(AnguloBuscado::Encontrado(theta)) => theta,
+            otro => panic!("Se esperaba un ángulo y se obtuvo {otro:?}"),
+        }
+    }
+
+    #[test]
+    fn test_recupera_el_angulo_del_ejemplo() {
+        // El ejemplo del enunciado a 30° cubre aproximadamente 446.41
+        let flatlanders = vec![
+            Flatlander::new(50, 150, 45),
+            Flatlander::new(0, 100, 45),
+            Flatlander::new(100, 200, 45),
+        ];
+
+        let theta = angulo_encontrado(angulo_para_longitud(
+            &flatlanders,
+            446.4101615137755,
+            &(10.0..=80.0),
+        ));
+        assert!((theta - 30.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fuera_de_alcance_informa_el_rango() {
+        let flatlanders = vec![Flatlander::new(0, 10, 45)];
+
+        // A 80° la sombra mide 10 / tan(80°) y a 10° mide 10 / tan(10°)
+        match angulo_para_longitud(&flatlanders, 1.0, &(10.0..=80.0)) {
+            Ok(AnguloBuscado::FueraDeAlcance { minima, maxima }) => {
+                assert!((minima - 1.763269807).abs() < 1e-6);
+                assert!((maxima - 56.712818196).abs() < 1e-6);
+            }
+            otro => panic!("Se esperaba un rango y se obtuvo {otro:?}"),
+        }
+    }
+
+    #[test]
+    fn test_rango_invalido() {
+        let flatlanders = vec![Flatlander::new(0, 10, 45)];
+
+        for rango in [0.0..=45.0, 60.0..=30.0, 45.0..=120.0] {
+            assert_eq!(
+                angulo_para_longitud(&flatlanders, 10.0, &rango),
+                Err(crate::ErrorTipo::ArgumentoInvalido)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fraccion_de_ventana_con_flatlander_flotante() {
+        // Un globo entre 10 y 20 de altura: a 45° sombrea [10, 20] y más arriba la sombra
+        // se achica hacia el poste, así que un cuarto de la ventana es una sombra de [5, 10]
+        let flatlanders = vec![Flatlander::new(0, 20, 45).con_elevacion(10).unwrap()];
+        let ventana = Intervalo {
+            inicio: 0.0,
+            fin: 20.0,
+        };
+
+        let theta = angulo_encontrado(angulo_para_fraccion(
+            &flatlanders,
+            &ventana,
+            0.25,
+            &(45.0..=80.0),
+        ));
+        let sombra = flatlanders[0].sombra_con_angulo(theta);
+        assert!((sombra.inicio - 5.0).abs() < 1e-6);
+        assert!((sombra.fin - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_maximo_interior_con_flatlander_flotante() {
+        // Un globo entre 100 y 110 sobre el origen y un poste de 10 en 100: a 45° sus
+        // sombras coinciden y miden 10, a 80° miden unos 3.53 en total, pero cerca de
+        // 47.7° quedan una junto a la otra y suman más de 18
+        let flatlanders = vec![
+            Flatlander::new(0, 110, 45).con_elevacion(100).unwrap(),
+            Flatlander::new(100, 10, 45),
+        ];
+
+        let theta = angulo_encontrado(angulo_para_longitud(&flatlanders, 15.0, &(45.0..=80.0)));
+        let mut sombras: Vec<Intervalo> = flatlanders
+            .iter()
+            .map(|f| f.sombra_con_angulo(theta))
+            .collect();
+        assert!((calcular_area(&mut sombras) - 15.0).abs() < 1e-6);
+
+        match angulo_para_longitud(&flatlanders, 20.0, &(45.0..=80.0)) {
+            Ok(AnguloBuscado::FueraDeAlcance { maxima, .. }) => assert!(maxima > 18.0),
+            otro => panic!("Se esperaba un rango y se obtuvo {otro:?}"),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::flatlanders::longitud_de_sombra;
+use crate::limites::Limites;
+use crate::parametros_espaciales::{ParametrosEspaciales, leer_valor};
+use crate::poligono::Poligono;
+
+/// Caja alineada con los ejes, apoyada sobre el plano del suelo.
+///
+/// Es la versión en tres dimensiones de un flatlander: ocupa el rectángulo
+/// `[x, x + ancho] x [y, y + largo]` del suelo y tiene altura `h`.
+///
+/// # Campos
+///
+/// * `x` - La posición de la esquina sudoeste en el eje X (hacia el este)
+/// * `y` - La posición de la esquina sudoeste en el eje Y (hacia el norte)
+/// * `ancho` - La medida de la caja en el eje X
+/// * `largo` - La medida de la caja en el eje Y
+/// * `h` - La altura de la caja
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::caja::Caja;
+///
+/// let caja = Caja { x: 0, y: 0, ancho: 2, largo: 2, h: 10 };
+///
+/// // Con el sol al oeste (azimut 180°) a 45° la sombra se estira 10 hacia el este
+/// let sombra = caja.sombra(180, 45);
+/// assert!((sombra.area() - 24.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Caja {
+    pub x: u32,
+    pub y: u32,
+    pub ancho: u32,
+    pub largo: u32,
+    pub h: u32,
+}
+
+impl Caja {
+    /// Calcula el polígono de sombra de la caja sobre el suelo, incluyendo su base.
+    ///
+    /// La sombra es la envolvente de la base y de la tapa proyectada sobre el suelo, que
+    /// es la base corrida por `desplazamiento_de_sombra`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `azimut` - La dirección hacia el sol en grados, medida desde el este en sentido antihorario
+    /// * `elevacion` - El ángulo del sol sobre el horizonte en grados
+    pub fn sombra(&self, azimut: u32, elevacion: u32) -> Poligono {
+        let (dx, dy) = desplazamiento_de_sombra(self.h as f64, azimut, elevacion);
+        let (x0, y0) = (self.x as f64, self.y as f64);
+        let (x1, y1) = (x0 + self.ancho as f64, y0 + self.largo as f64);
+
+        let puntos = [(x0, y0), (x1, y0), (x1, y1), (x0, y1)]
+            .iter()
+            .flat_map(|&(x, y)| [(x, y), (x + dx, y + dy)])
+            .collect();
+        Poligono::envolvente_convexa(puntos)
+    }
+}
+
+/// Calcula cuánto se corre sobre el suelo la sombra de un punto a altura `h`.
+///
+/// La sombra cae del lado opuesto al sol, a la distancia que da `longitud_de_sombra`
+/// con la elevación como ángulo. Con azimut 180° (sol al oeste) coincide con la sombra
+/// de un flatlander en el camino.
+///
+/// # Argumentos
+///
+/// * `h` - La altura del punto
+/// * `azimut` - La dirección hacia el sol en grados, medida desde el este en sentido antihorario
+/// * `elevacion` - El ángulo del sol sobre el horizonte en grados
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::caja::desplazamiento_de_sombra;
+///
+/// // Sol al sur a 45°: la sombra cae hacia el norte
+/// let (dx, dy) = desplazamiento_de_sombra(10.0, 270, 45);
+/// assert!(dx.abs() < 1e-9 && (dy - 10.0).abs() < 1e-9);
+/// ```
+pub fn desplazamiento_de_sombra(h: f64, azimut: u32, elevacion: u32) -> (f64, f64) {
+    let longitud = longitud_de_sombra(h, elevacion);
+    let direccion = (azimut as f64).to_radians();
+    (-longitud * direccion.cos(), -longitud * direccion.sin())
+}
+
+/// Lee y valida las `n` cajas, una por línea con el formato `x y ancho largo h`.
+///
+/// Las posiciones se validan con el rango de `x`, las medidas de la base con el de `w`
+/// y la altura con el de `h`.
+///
+/// # Argumentos
+///
+/// * `lineas` - Un iterador mutable sobre las líneas de entrada
+/// * `params` - Los parámetros que contienen el número de cajas
+/// * `limites` - Los rangos válidos para cada valor
+///
+/// # Errores
+///
+/// * `ErrorTipo::IO` - Error de entrada/salida al leer
+/// * `ErrorTipo::LineaFaltante` - No hay suficientes líneas de entrada
+/// * `ErrorTipo::ValorFaltante` - Una línea no tiene exactamente 5 valores
+/// * `ErrorTipo::NumeroInvalido` - Un valor no se puede parsear como número
+/// * `ErrorTipo::FueraDeRango` - Un valor está fuera del rango permitido
+pub fn leer_cajas_desde_stdin_con_limites<R: std::io::BufRead>(
+    lineas: &mut std::io::Lines<R>,
+    params: &ParametrosEspaciales,
+    limites: &Limites,
+) -> Result<Vec<Caja>, crate::ErrorTipo> {
+    let mut cajas = Vec::with_capacity(params.n as usize);
+
+    for _ in 0..params.n {
+        let linea = match lineas.next() {
+            Some(Ok(linea)) => linea,
+            Some(Err(_)) => return Err(crate::ErrorTipo::IO),
+            None => return Err(crate::ErrorTipo::LineaFaltante),
+        };
+
+        let tokens: Vec<&str> = linea.split_whitespace().collect();
+        let [x, y, ancho, largo, h] = tokens[..] else {
+            return Err(crate::ErrorTipo::ValorFaltante);
+        };
+
+        cajas.push(Caja {
+            x: leer_valor(x, &limites.x)?,
+            y: leer_valor(y, &limites.x)?,
+            ancho: leer_valor(ancho, &limites.w)?,
+            largo: leer_valor(largo, &limites.w)?,
+            h: leer_valor(h, &limites.h)?,
+        });
+    }
+
+    Ok(cajas)
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::flatlanders::Flatlander;
+
+    #[test]
+    fn test_coincide_con_flatlander() {
+        // Una caja de largo 1 con el sol al oeste da una sombra de área igual a la longitud
+        let caja = Caja {
+            x: 10,
+            y: 0,
+            ancho: 0,
+            largo: 1,
+            h: 20,
+        };
+        let flatlander = Flatlander::new(10, 20, 30);
+        let sombra = flatlander.sombra();
+
+        let rango = caja.sombra(180, 30).rango_x();
+        assert!((rango.inicio - sombra.inicio).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((rango.fin - sombra.fin).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((caja.sombra(180, 30).area() - flatlander.l).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_sol_vertical_solo_la_base() {
+        let caja = Caja {
+            x: 0,
+            y: 0,
+            ancho: 3,
+            largo: 4,
+            h: 50,
+        };
+        assert_eq!(caja.sombra(0, 90).area(), 12.0);
+    }
+
+    #[test]
+    fn test_sombra_en_diagonal() {
+        // Caja de 2x2 con el desplazamiento (-3, -4) (sol al noreste)
+        let caja = Caja {
+            x: 0,
+            y: 0,
+            ancho: 2,
+            largo: 2,
+            h: 5,
+        };
+        let azimut = 53; // Aproximadamente atan2(4, 3)
+        let (dx, dy) = desplazamiento_de_sombra(5.0, azimut, 45);
+
+        // Base más lo barrido: 4 + |d x (2, 0)| + |d x (0, 2)|
+        let esperada = 4.0 + 2.0 * dy.abs() + 2.0 * dx.abs();
+        assert!((caja.sombra(azimut, 45).area() - esperada).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_leer_cajas() {
+        use std::io::BufRead;
+
+        let params = ParametrosEspaciales {
+            azimut: 180,
+            elevacion: 45,
+            n: 2,
+        };
+        let leer = |entrada: &str| {
+            let mut lineas = std::io::BufReader::new(entrada.as_bytes()).lines();
+            leer_cajas_desde_stdin_con_limites(&mut lineas, &params, &Limites::concurso())
+        };
+
+        let cajas = leer("0 0 2 3 10\n5 6 1 1 20\n").unwrap();
+        assert_eq!(
+            cajas[1],
+            Caja {
+                x: 5,
+                y: 6,
+                ancho: 1,
+                largo: 1,
+                h: 20
+            }
+        );
+        assert!(matches!(
+            leer("0 0 2 3\n5 6 1 1 20\n"),
+            Err(crate::ErrorTipo::ValorFaltante)
+        ));
+        assert!(matches!(
+            leer("0 0 2 3 0\n5 6 1 1 20\n"),
+            Err(crate::ErrorTipo::FueraDeRango)
+        ));
+        assert!(matches!(
+            leer("0 0 2 3 10\n"),
+            Err(crate::ErrorTipo::LineaFaltante)
+        ));
+    }
+}
@@ -0,0 +1,370 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::intervalos::{Intervalo, calcular_huecos_iluminados};
+
+/// Conjunto de sombras que se mantiene normalizado a medida que se le insertan intervalos.
+///
+/// Internamente guarda los intervalos ya fusionados y disjuntos en un árbol ordenado por
+/// su inicio. Insertar una sombra nueva cuesta `O(log n)` más la cantidad de intervalos
+/// que absorbe, por lo que no hace falta volver a ordenar todo el conjunto en cada
+/// actualización.
+///
+/// # Campos
+///
+/// * `intervalos` - El fin de cada intervalo fusionado, indexado por su inicio
+/// * `longitud` - La longitud total cubierta por los intervalos
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::conjunto_de_sombras::ConjuntoDeSombras;
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+///
+/// let mut sombras = ConjuntoDeSombras::new();
+/// sombras.insertar(Intervalo { inicio: 0.0, fin: 10.0 });
+/// assert_eq!(sombras.longitud_cubierta(), 10.0);
+///
+/// sombras.insertar(Intervalo { inicio: 5.0, fin: 15.0 });
+/// assert_eq!(sombras.longitud_cubierta(), 15.0); // Se fusionan en [0, 15]
+/// ```
+#[derive(Debug, Default)]
+pub struct ConjuntoDeSombras {
+    intervalos: BTreeMap<Inicio, f64>,
+    longitud: f64,
+}
+
+/// Inicio de un intervalo usado como clave, ordenado con `f64::total_cmp`.
+#[derive(Clone, Copy, Debug)]
+struct Inicio(f64);
+
+impl PartialEq for Inicio {
+    fn eq(&self, otro: &Self) -> bool {
+        self.cmp(otro) == Ordering::Equal
+    }
+}
+
+impl Eq for Inicio {}
+
+impl PartialOrd for Inicio {
+    fn partial_cmp(&self, otro: &Self) -> Option<Ordering> {
+        Some(self.cmp(otro))
+    }
+}
+
+impl Ord for Inicio {
+    fn cmp(&self, otro: &Self) -> Ordering {
+        self.0.total_cmp(&otro.0)
+    }
+}
+
+impl ConjuntoDeSombras {
+    /// Crea un conjunto de sombras vacío.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserta un intervalo en el conjunto, fusionándolo con los que se superponen.
+    ///
+    /// Los intervalos que solo se tocan en un extremo también se fusionan, igual que en
+    /// `calcular_area`. Los intervalos con `fin < inicio` se ignoran.
+    ///
+    /// # Argumentos
+    ///
+    /// * `intervalo` - El intervalo de sombra a insertar
+    pub fn insertar(&mut self, intervalo: Intervalo) {
+        if intervalo.fin < intervalo.inicio {
+            return;
+        }
+        let mut fusionado = intervalo;
+
+        // Solo el último intervalo que empieza antes puede llegar hasta el nuevo, porque
+        // los guardados son disjuntos
+        let anterior = self
+            .intervalos
+            .range(..=Inicio(fusionado.inicio))
+            .next_back();
+        if let Some((&inicio, &fin)) = anterior
+            && fin >= fusionado.inicio
+        {
+            self.intervalos.remove(&inicio);
+            self.longitud -= fin - inicio.0;
+            fusionado.inicio = inicio.0;
+            fusionado.fin = fusionado.fin.max(fin);
+        }
+
+        // Los que empiezan dentro del nuevo quedan absorbidos
+        while let Some((&inicio, &fin)) = self
+            .intervalos
+            .range(Inicio(fusionado.inicio)..=Inicio(fusionado.fin))
+            .next()
+        {
+            self.intervalos.remove(&inicio);
+            self.longitud -= fin - inicio.0;
+            fusionado.fin = fusionado.fin.max(fin);
+        }
+
+        self.longitud += fusionado.fin - fusionado.inicio;
+        self.intervalos
+            .insert(Inicio(fusionado.inicio), fusionado.fin);
+    }
+
+    /// Retorna la longitud total cubierta por las sombras insertadas hasta el momento.
+    ///
+    /// Se lleva al día en cada inserción, así que no recorre los intervalos. Al sumar y
+    /// restar longitudes el redondeo puede diferir del de `calcular_area`; para el valor
+    /// que da la suma en orden se puede usar `longitud_total` sobre `into_intervalos`.
+    pub fn longitud_cubierta(&self) -> f64 {
+        self.longitud
+    }
+
+    /// Retorna la longitud cubierta por las sombras dentro de una ventana del camino.
+    ///
+    /// Solo recorre los intervalos que se superponen con la ventana.
+    ///
+    /// # Argumentos
+    ///
+    /// * `ventana` - El tramo del camino a analizar
+    pub fn longitud_cubierta_en(&self, ventana: &Intervalo) -> f64 {
+        self.intervalos_en(ventana)
+            .map(|tramo| (tramo.fin.min(ventana.fin) - tramo.inicio.max(ventana.inicio)).max(0.0))
+            .sum()
+    }
+
+    /// Recorre en orden los intervalos que pueden superponerse con la ventana: el último
+    /// que empieza antes que ella y los que empiezan dentro.
+    fn intervalos_en(&self, ventana: &Intervalo) -> impl Iterator<Item = Intervalo> + '_ {
+        let anterior = self.intervalos.range(..Inicio(ventana.inicio)).next_back();
+        let dentro = self
+            .intervalos
+            .range(Inicio(ventana.inicio)..=Inicio(ventana.fin));
+
+        anterior
+            .into_iter()
+            .chain(dentro)
+            .map(|(inicio, &fin)| Intervalo {
+                inicio: inicio.0,
+                fin,
+            })
+    }
+
+    /// Retorna los intervalos fusionados, disjuntos y ordenados por inicio.
+    pub fn intervalos(&self) -> Vec<Intervalo> {
+        self.intervalos
+            .iter()
+            .map(|(inicio, &fin)| Intervalo {
+                inicio: inicio.0,
+                fin,
+            })
+            .collect()
+    }
+
+    /// Retorna los tramos iluminados dentro de una ventana del camino.
+    ///
+    /// Ver `calcular_huecos_iluminados` para más detalles.
+    ///
+    /// # Argumentos
+    ///
+    /// * `ventana` - El tramo del camino a analizar
+    pub fn huecos_iluminados(&self, ventana: &Intervalo) -> Vec<Intervalo> {
+        let cercanos: Vec<Intervalo> = self.intervalos_en(ventana).collect();
+        calcular_huecos_iluminados(&cercanos, ventana)
+    }
+
+    /// Consume el conjunto y retorna sus intervalos fusionados, disjuntos y ordenados por inicio.
+    pub fn into_intervalos(self) -> Vec<Intervalo> {
+        self.intervalos
+            .into_iter()
+            .map(|(inicio, fin)| Intervalo {
+                inicio: inicio.0,
+                fin,
+            })
+            .collect()
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intervalos::calcular_area;
+
+    fn conjunto_con(intervalos: &[(f64, f64)]) -> ConjuntoDeSombras {
+        let mut sombras = ConjuntoDeSombras::new();
+        for &(inicio, fin) in intervalos {
+            sombras.insertar(Intervalo { inicio, fin });
+        }
+        sombras
+    }
+
+    #[test]
+    fn test_conjunto_vacio() {
+        let sombras = ConjuntoDeSombras::new();
+        assert_eq!(sombras.longitud_cubierta(), 0.0);
+        assert!(sombras.intervalos().is_empty());
+    }
+
+    #[test]
+    fn test_insertar_sin_superposicion_desordenados() {
+        let sombras = conjunto_con(&[(10.0, 15.0), (0.0, 5.0), (20.0, 22.0)]);
+
+        assert_eq!(sombras.longitud_cubierta(), 12.0);
+        assert_eq!(
+            sombras.intervalos(),
+            vec![
+                Intervalo {
+                    inicio: 0.0,
+                    fin: 5.0
+                },
+                Intervalo {
+                    inicio: 10.0,
+                    fin: 15.0
+                },
+                Intervalo {
+                    inicio: 20.0,
+                    fin: 22.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insertar_intervalo_que_une_varios() {
+        let sombras = conjunto_con(&[(0.0, 5.0), (10.0, 15.0), (20.0, 25.0), (3.0, 21.0)]);
+
+        assert_eq!(sombras.longitud_cubierta(), 25.0);
+        assert_eq!(
+            sombras.intervalos(),
+            vec![Intervalo {
+                inicio: 0.0,
+                fin: 25.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_insertar_intervalos_que_se_tocan() {
+        let sombras = conjunto_con(&[(0.0, 5.0), (5.0, 10.0)]);
+
+        assert_eq!(sombras.longitud_cubierta(), 10.0);
+        assert_eq!(sombras.intervalos().len(), 1);
+    }
+
+    #[test]
+    fn test_insertar_intervalo_contenido() {
+        let sombras = conjunto_con(&[(0.0, 20.0), (5.0, 15.0)]);
+
+        assert_eq!(sombras.longitud_cubierta(), 20.0);
+        assert_eq!(
+            sombras.intervalos(),
+            vec![Intervalo {
+                inicio: 0.0,
+                fin: 20.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_huecos_iluminados_en_ventana() {
+        let sombras = conjunto_con(&[(10.0, 15.0), (0.0, 5.0)]);
+        let ventana = Intervalo {
+            inicio: 3.0,
+            fin: 20.0,
+        };
+
+        assert_eq!(
+            sombras.huecos_iluminados(&ventana),
+            vec![
+                Intervalo {
+                    inicio: 5.0,
+                    fin: 10.0
+                },
+                Intervalo {
+                    inicio: 15.0,
+                    fin: 20.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_longitud_al_dia_en_cada_insercion() {
+        // Secuencia pseudoaleatoria fija, seguida de una descendente que se encadena
+        let mut semilla: u64 = 12345;
+        let mut siguiente = || {
+            semilla = semilla
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (semilla >> 33) as f64 / 1e4
+        };
+        let mut insertados: Vec<Intervalo> = (0..500)
+            .map(|_| {
+                let inicio = siguiente();
+                Intervalo {
+                    inicio,
+                    fin: inicio + siguiente() / 100.0,
+                }
+            })
+            .collect();
+        insertados.extend((0..500).rev().map(|i| Intervalo {
+            inicio: i as f64 * 2.5,
+            fin: i as f64 * 2.5 + 3.0,
+        }));
+
+        let mut sombras = ConjuntoDeSombras::new();
+        for (i, intervalo) in insertados.iter().enumerate() {
+            sombras.insertar(*intervalo);
+            let esperada = calcular_area(&mut insertados[..=i].to_vec());
+            assert!((sombras.longitud_cubierta() - esperada).abs() <= 1e-9 * esperada.max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_longitud_cubierta_en_ventana() {
+        let sombras = conjunto_con(&[(0.0, 5.0), (10.0, 15.0), (20.0, 22.0)]);
+        let ventana = Intervalo {
+            inicio: 3.0,
+            fin: 12.0,
+        };
+
+        assert_eq!(sombras.longitud_cubierta_en(&ventana), 4.0);
+        assert_eq!(
+            sombras.longitud_cubierta_en(&Intervalo {
+                inicio: 16.0,
+                fin: 19.0
+            }),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_insertar_intervalo_invertido_se_ignora() {
+        let sombras = conjunto_con(&[(0.0, 5.0), (10.0, 3.0)]);
+
+        assert_eq!(sombras.longitud_cubierta(), 5.0);
+        assert_eq!(sombras.intervalos().len(), 1);
+    }
+
+    #[test]
+    fn test_insertar_descendentes_que_se_encadenan() {
+        let pares: Vec<(f64, f64)> = (0..1000)
+            .rev()
+            .map(|i| (i as f64 * 2.0, i as f64 * 2.0 + 2.0))
+            .collect();
+        let sombras = conjunto_con(&pares);
+
+        assert_eq!(sombras.longitud_cubierta(), 2000.0);
+        assert_eq!(
+            sombras.intervalos(),
+            vec![Intervalo {
+                inicio: 0.0,
+                fin: 2000.0
+            }]
+        );
+    }
+}
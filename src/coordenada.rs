@@ -0,0 +1,67 @@
+use crate::racional::Racional;
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+/// Tipo numérico que puede usarse como extremo de un `Intervalo`.
+///
+/// El algoritmo de fusión solo necesita ordenar, sumar y restar extremos, por lo que
+/// sirve tanto para `f64` como para tipos exactos: enteros o `Racional`.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, calcular_area};
+///
+/// let mut ticks: Vec<Intervalo<u64>> = vec![
+///     Intervalo { inicio: 0, fin: 10 },
+///     Intervalo { inicio: 5, fin: 15 },
+/// ];
+/// assert_eq!(calcular_area(&mut ticks), 15);
+/// ```
+pub trait Coordenada: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    /// Retorna el cero del tipo, desde el cual se acumulan las longitudes.
+    fn cero() -> Self;
+
+    /// Compara dos valores con un orden total, usado para ordenar los intervalos.
+    fn comparar(&self, otro: &Self) -> Ordering;
+}
+
+impl Coordenada for f64 {
+    fn cero() -> Self {
+        0.0
+    }
+
+    fn comparar(&self, otro: &Self) -> Ordering {
+        self.total_cmp(otro)
+    }
+}
+
+impl Coordenada for i64 {
+    fn cero() -> Self {
+        0
+    }
+
+    fn comparar(&self, otro: &Self) -> Ordering {
+        self.cmp(otro)
+    }
+}
+
+impl Coordenada for u64 {
+    fn cero() -> Self {
+        0
+    }
+
+    fn comparar(&self, otro: &Self) -> Ordering {
+        self.cmp(otro)
+    }
+}
+
+impl Coordenada for Racional {
+    fn cero() -> Self {
+        Racional::entero(0)
+    }
+
+    fn comparar(&self, otro: &Self) -> Ordering {
+        self.cmp(otro)
+    }
+}
@@ -0,0 +1,238 @@
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{Intervalo, fusionar_intervalos, intersecar_intervalos, longitud_total};
+use crate::modelo_de_luz::ModeloDeLuz;
+
+/// Escena con varios flatlanders iluminados por varias fuentes de luz a la vez.
+///
+/// # Campos
+///
+/// * `flatlanders` - Los flatlanders parados sobre el camino
+/// * `luces` - Las fuentes de luz que iluminan la escena
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::escena::Escena;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::modelo_de_luz::ModeloDeLuz;
+///
+/// // Un sol al oeste y otro al este, a 45° del horizonte
+/// let escena = Escena {
+///     flatlanders: vec![Flatlander::new(0, 10, 45)],
+///     luces: vec![
+///         ModeloDeLuz::Sol { theta: AnguloDelSol::Grados(45.0) },
+///         ModeloDeLuz::Sol { theta: AnguloDelSol::Grados(135.0) },
+///     ],
+/// };
+///
+/// let cobertura = escena.cobertura();
+/// // Sombras de [0, 10] y [-10, 0], que no se superponen
+/// assert!((cobertura.union - 20.0).abs() < 1e-9);
+/// assert_eq!(cobertura.umbra, 0.0);
+/// assert_eq!(cobertura.por_fuente.len(), 2);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Escena {
+    pub flatlanders: Vec<Flatlander>,
+    pub luces: Vec<ModeloDeLuz>,
+}
+
+impl Escena {
+    /// Crea una escena iluminada por varios soles, uno por cada ángulo.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders parados sobre el camino
+    /// * `thetas` - Los ángulos de cada sol, medidos desde el horizonte oeste
+    pub fn con_soles(flatlanders: Vec<Flatlander>, thetas: &[AnguloDelSol]) -> Self {
+        Self {
+            flatlanders,
+            luces: thetas
+                .iter()
+                .map(|&theta| ModeloDeLuz::Sol { theta })
+                .collect(),
+        }
+    }
+
+    /// Calcula los tramos del camino en sombra respecto de una de las fuentes.
+    ///
+    /// # Retorna
+    ///
+    /// Los tramos fusionados, con extremos infinitos para las sombras sin fin.
+    pub fn sombras_de(&self, luz: &ModeloDeLuz) -> Vec<Intervalo> {
+        sombras_de(&self.flatlanders, luz)
+    }
+
+    /// Calcula la longitud en sombra respecto de alguna fuente, de todas y de cada una.
+    ///
+    /// Ver `calcular_cobertura` para más detalles.
+    pub fn cobertura(&self) -> CoberturaPorFuentes {
+        calcular_cobertura(&self.flatlanders, &self.luces)
+    }
+}
+
+/// Calcula los tramos fusionados del camino en sombra respecto de una fuente.
+fn sombras_de(flatlanders: &[Flatlander], luz: &ModeloDeLuz) -> Vec<Intervalo> {
+    let mut sombras: Vec<Intervalo> = flatlanders
+        .iter()
+        .map(|f| f.sombra_con_luz(luz).como_intervalo())
+        .collect();
+    fusionar_intervalos(&mut sombras)
+}
+
+/// Calcula la longitud en sombra respecto de alguna fuente, de todas y de cada una, sin
+/// armar una `Escena` con los flatlanders.
+///
+/// Una escena sin fuentes de luz no tiene sombras, así que todas las longitudes valen cero.
+///
+/// # Argumentos
+///
+/// * `flatlanders` - Los flatlanders parados sobre el camino
+/// * `luces` - Las fuentes de luz que iluminan la escena
+pub fn calcular_cobertura(
+    flatlanders: &[Flatlander],
+    luces: &[ModeloDeLuz],
+) -> CoberturaPorFuentes {
+    let por_luz: Vec<Vec<Intervalo>> = luces
+        .iter()
+        .map(|luz| sombras_de(flatlanders, luz))
+        .collect();
+
+    let mut todas: Vec<Intervalo> = por_luz.iter().flatten().copied().collect();
+    let union = longitud_total(&fusionar_intervalos(&mut todas));
+
+    let umbra = por_luz.split_first().map_or(0.0, |(primera, resto)| {
+        let comun = resto.iter().fold(primera.to_vec(), |comun, otra| {
+            intersecar_intervalos(&comun, otra)
+        });
+        longitud_total(&comun)
+    });
+
+    CoberturaPorFuentes {
+        union,
+        umbra,
+        por_fuente: por_luz
+            .iter()
+            .map(|tramos| longitud_total(tramos))
+            .collect(),
+    }
+}
+
+/// Longitudes del camino en sombra cuando la escena tiene varias fuentes de luz.
+///
+/// Un punto está en sombra respecto de una fuente si algún flatlander le tapa esa luz.
+/// Con fuentes a distancia finita las sombras pueden no tener fin, y entonces las
+/// longitudes que las incluyen son infinitas.
+///
+/// # Campos
+///
+/// * `union` - Longitud en sombra respecto de al menos una fuente
+/// * `umbra` - Longitud en sombra respecto de todas las fuentes a la vez
+/// * `por_fuente` - Longitud en sombra respecto de cada fuente, en el orden de la escena
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::escena::CoberturaPorFuentes;
+///
+/// let cobertura = CoberturaPorFuentes {
+///     union: 30.0,
+///     umbra: 10.0,
+///     por_fuente: vec![20.0, 20.0],
+/// };
+/// assert_eq!(cobertura.penumbra(), 20.0);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct CoberturaPorFuentes {
+    pub union: f64,
+    pub umbra: f64,
+    pub por_fuente: Vec<f64>,
+}
+
+impl CoberturaPorFuentes {
+    /// Retorna la longitud en sombra respecto de algunas fuentes pero no de todas.
+    pub fn penumbra(&self) -> f64 {
+        self.union - self.umbra
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::flatlanders::crear_intervalo_de_flatlanders;
+    use crate::intervalos::calcular_area;
+
+    #[test]
+    fn test_un_sol_coincide_con_calcular_area() {
+        let escena = Escena::con_soles(
+            vec![
+                Flatlander::new(50, 150, 30),
+                Flatlander::new(0, 100, 30),
+                Flatlander::new(100, 200, 30),
+            ],
+            &[AnguloDelSol::Grados(30.0)],
+        );
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(30.0),
+        };
+        let mut sombras = crear_intervalo_de_flatlanders(&escena.flatlanders, &sol).unwrap();
+        let area = calcular_area(&mut sombras);
+
+        let cobertura = escena.cobertura();
+        assert!((cobertura.union - area).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((cobertura.umbra - area).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert_eq!(cobertura.por_fuente.len(), 1);
+    }
+
+    #[test]
+    fn test_umbra_de_dos_soles_del_mismo_lado() {
+        // Sombras de [0, 10] con 45° y de [0, 10 / tan(60°)] con 60°
+        let escena = Escena::con_soles(
+            vec![Flatlander::new(0, 10, 45)],
+            &[AnguloDelSol::Grados(45.0), AnguloDelSol::Grados(60.0)],
+        );
+        let corta = 10.0 / 60.0_f64.to_radians().tan();
+
+        let cobertura = escena.cobertura();
+        assert!((cobertura.union - 10.0).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((cobertura.umbra - corta).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((cobertura.por_fuente[1] - corta).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_lampara_con_sombra_sin_fin() {
+        let escena = Escena {
+            flatlanders: vec![Flatlander::new(10, 20, 45)],
+            luces: vec![
+                ModeloDeLuz::Sol {
+                    theta: AnguloDelSol::Grados(45.0),
+                },
+                ModeloDeLuz::Lampara { x: 0.0, y: 5.0 },
+            ],
+        };
+
+        let cobertura = escena.cobertura();
+        assert_eq!(cobertura.union, f64::INFINITY);
+        assert!((cobertura.umbra - 20.0).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert_eq!(cobertura.por_fuente[1], f64::INFINITY);
+    }
+
+    #[test]
+    fn test_escena_sin_luces() {
+        let escena = Escena::con_soles(vec![Flatlander::new(0, 10, 45)], &[]);
+        let cobertura = escena.cobertura();
+
+        assert_eq!(cobertura.union, 0.0);
+        assert_eq!(cobertura.umbra, 0.0);
+        assert!(cobertura.por_fuente.is_empty());
+    }
+}
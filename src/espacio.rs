@@ -0,0 +1,227 @@
+use crate::intervalos::{Intervalo, fusionar_ordenados, longitud_total};
+use crate::poligono::Poligono;
+
+/// Calcula el área de la unión de varios polígonos convexos con un barrido del plano.
+///
+/// Las posiciones `x` de los vértices y de los cruces entre lados de polígonos distintos
+/// parten el plano en franjas verticales. Dentro de cada franja ningún lado se cruza con
+/// otro, así que la longitud cubierta sobre una recta vertical varía linealmente con `x`
+/// y alcanza con medirla en el centro de la franja. Esa medición es el barrido en una
+/// dimensión de `calcular_area` sobre los cortes verticales de los polígonos.
+///
+/// El barrido lleva los polígonos activos, los que atraviesan la franja actual, así que
+/// cada franja solo corta esos, y los cruces solo se buscan entre polígonos cuyos rangos
+/// de `x` y de `y` se superponen.
+///
+/// # Argumentos
+///
+/// * `poligonos` - Los polígonos a unir
+///
+/// # Retorna
+///
+/// El área cubierta por al menos un polígono.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::espacio::calcular_area_de_union;
+/// use el_rompecabezas_de_las_sombreas::poligono::Poligono;
+///
+/// let cuadrado = |x: f64, y: f64| {
+///     Poligono::envolvente_convexa(vec![(x, y), (x + 2.0, y), (x + 2.0, y + 2.0), (x, y + 2.0)])
+/// };
+///
+/// // Dos cuadrados de 2x2 que comparten un cuadrado de 1x1
+/// let area = calcular_area_de_union(&[cuadrado(0.0, 0.0), cuadrado(1.0, 1.0)]);
+/// assert!((area - 7.0).abs() < 1e-9);
+/// ```
+pub fn calcular_area_de_union(poligonos: &[Poligono]) -> f64 {
+    let rangos: Vec<Intervalo> = poligonos.iter().map(Poligono::rango_x).collect();
+    let mut orden: Vec<usize> = (0..poligonos.len()).collect();
+    orden.sort_by(|&a, &b| rangos[a].inicio.total_cmp(&rangos[b].inicio));
+
+    let mut eventos: Vec<f64> = poligonos
+        .iter()
+        .flat_map(|poligono| poligono.vertices().iter().map(|v| v.0))
+        .collect();
+    eventos.extend(cruces_entre_poligonos(poligonos, &rangos, &orden));
+    eventos.sort_by(f64::total_cmp);
+    eventos.dedup();
+
+    // Solo se cortan los polígonos activos, los que atraviesan la franja actual
+    let mut activos: Vec<usize> = Vec::new();
+    let mut siguiente = 0;
+    let mut area = 0.0;
+    for franja in eventos.windows(2) {
+        let (a, b) = (franja[0], franja[1]);
+        let centro = (a + b) / 2.0;
+        while let Some(&i) = orden.get(siguiente)
+            && rangos[i].inicio <= centro
+        {
+            activos.push(i);
+            siguiente += 1;
+        }
+        activos.retain(|&i| rangos[i].fin >= centro);
+
+        // Los activos quedan ordenados por su corte en la franja anterior, y el orden de
+        // los cortes solo cambia en los cruces, así que ordenarlos de nuevo es casi lineal
+        let mut cortes: Vec<(Intervalo, usize)> = activos
+            .iter()
+            .filter_map(|&i| Some((poligonos[i].corte_vertical(centro)?, i)))
+            .collect();
+        cortes.sort_by(|a, b| a.0.inicio.total_cmp(&b.0.inicio));
+        activos = cortes.iter().map(|&(_, i)| i).collect();
+
+        let cortes: Vec<Intervalo> = cortes.into_iter().map(|(corte, _)| corte).collect();
+        area += (b - a) * longitud_total(&fusionar_ordenados(&cortes));
+    }
+    area
+}
+
+/// Retorna las posiciones `x` donde se cruzan lados de polígonos distintos.
+///
+/// Barre los polígonos en el orden de `orden`, por el inicio de su rango de `x`,
+/// llevando los activos: los que empezaron antes y todavía no terminaron. Solo se
+/// comparan los lados de un polígono con los de los activos cuyo rango de `y` también
+/// se superpone con el suyo, y de esos solo los pares de lados cuyas cajas se tocan.
+///
+/// # Argumentos
+///
+/// * `poligonos` - Los polígonos a unir
+/// * `rangos` - El rango de `x` de cada polígono
+/// * `orden` - Los índices de los polígonos, ordenados por el inicio de su rango de `x`
+fn cruces_entre_poligonos(
+    poligonos: &[Poligono],
+    rangos: &[Intervalo],
+    orden: &[usize],
+) -> Vec<f64> {
+    let rangos_y: Vec<Intervalo> = poligonos.iter().map(rango_y).collect();
+    let mut activos: Vec<usize> = Vec::new();
+    let mut cruces = Vec::new();
+
+    for &i in orden {
+        activos.retain(|&j| rangos[j].fin >= rangos[i].inicio);
+        let vecinos = activos.iter().filter(|&&j| {
+            rangos_y[j].fin >= rangos_y[i].inicio && rangos_y[i].fin >= rangos_y[j].inicio
+        });
+        for &j in vecinos {
+            for lado_p in poligonos[i].lados() {
+                let cruces_del_lado = poligonos[j]
+                    .lados()
+                    .filter(|&lado_q| cajas_se_tocan(lado_p, lado_q))
+                    .filter_map(|lado_q| cruce_de_lados(lado_p, lado_q));
+                cruces.extend(cruces_del_lado);
+            }
+        }
+        activos.push(i);
+    }
+    cruces
+}
+
+/// Retorna el rango de `y` que ocupa un polígono.
+fn rango_y(poligono: &Poligono) -> Intervalo {
+    let ys = poligono.vertices().iter().map(|v| v.1);
+    Intervalo {
+        inicio: ys.clone().fold(f64::INFINITY, f64::min),
+        fin: ys.fold(f64::NEG_INFINITY, f64::max),
+    }
+}
+
+/// Indica si las cajas que encierran a dos lados se tocan, condición necesaria para que
+/// los lados se crucen.
+fn cajas_se_tocan((p, q): ((f64, f64), (f64, f64)), (r, s): ((f64, f64), (f64, f64))) -> bool {
+    p.0.max(q.0) >= r.0.min(s.0)
+        && r.0.max(s.0) >= p.0.min(q.0)
+        && p.1.max(q.1) >= r.1.min(s.1)
+        && r.1.max(s.1) >= p.1.min(q.1)
+}
+
+/// Retorna la posición `x` donde se cruzan dos lados, si se cruzan en un único punto.
+///
+/// Los lados paralelos no aportan eventos: si se superponen, sus extremos ya son vértices.
+fn cruce_de_lados(
+    (p, q): ((f64, f64), (f64, f64)),
+    (r, s): ((f64, f64), (f64, f64)),
+) -> Option<f64> {
+    let (dx1, dy1) = (q.0 - p.0, q.1 - p.1);
+    let (dx2, dy2) = (s.0 - r.0, s.1 - r.1);
+    let denominador = dx1 * dy2 - dy1 * dx2;
+    if denominador == 0.0 {
+        return None;
+    }
+
+    // p + t (q - p) = r + u (s - r), con t y u entre 0 y 1
+    let t = ((r.0 - p.0) * dy2 - (r.1 - p.1) * dx2) / denominador;
+    let u = ((r.0 - p.0) * dy1 - (r.1 - p.1) * dx1) / denominador;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then_some(p.0 + t * dx1)
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangulo(x: f64, y: f64, ancho: f64, alto: f64) -> Poligono {
+        Poligono::envolvente_convexa(vec![
+            (x, y),
+            (x + ancho, y),
+            (x + ancho, y + alto),
+            (x, y + alto),
+        ])
+    }
+
+    #[test]
+    fn test_union_vacia() {
+        assert_eq!(calcular_area_de_union(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_cruz_de_rectangulos() {
+        // Sin vértices de uno dentro del otro: solo los cruces de lados separan las franjas
+        let horizontal = rectangulo(0.0, 1.0, 3.0, 1.0);
+        let vertical = rectangulo(1.0, 0.0, 1.0, 3.0);
+
+        let area = calcular_area_de_union(&[horizontal, vertical]);
+        assert!((area - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_triangulos_cruzados() {
+        // Dos triángulos que forman una estrella de David de altura 3 y base 2 sqrt(3)
+        let raiz = 3f64.sqrt();
+        let arriba = Poligono::envolvente_convexa(vec![(-raiz, 0.0), (raiz, 0.0), (0.0, 3.0)]);
+        let abajo = Poligono::envolvente_convexa(vec![(-raiz, 2.0), (raiz, 2.0), (0.0, -1.0)]);
+
+        // Cada triángulo tiene área 3 sqrt(3) y el hexágono común, dos tercios de esa área
+        let esperada = 2.0 * 3.0 * raiz - 2.0 * raiz;
+        let area = calcular_area_de_union(&[arriba, abajo]);
+        assert!((area - esperada).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cantidad_de_poligonos_cerca_del_limite() {
+        // 100 000 cuadrados separados: cada franja corta a lo sumo un polígono activo
+        let separados: Vec<Poligono> = (0..100_000)
+            .map(|i| rectangulo(3.0 * i as f64, (i % 7) as f64, 1.0, 1.0))
+            .collect();
+        assert!((calcular_area_de_union(&separados) - 100_000.0).abs() < 1e-6);
+
+        // 20 000 cuadrados de 2x2 que se pisan con el siguiente, en una sola fila
+        let encadenados: Vec<Poligono> = (0..20_000)
+            .map(|i| rectangulo(i as f64, 0.0, 2.0, 2.0))
+            .collect();
+        assert!((calcular_area_de_union(&encadenados) - 2.0 * 20_001.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lados_paralelos_no_se_cruzan() {
+        let lado = ((0.0, 0.0), (1.0, 1.0));
+        assert_eq!(cruce_de_lados(lado, ((0.0, 1.0), (1.0, 2.0))), None);
+        assert_eq!(cruce_de_lados(lado, ((0.0, 1.0), (1.0, 0.0))), Some(0.5));
+    }
+}
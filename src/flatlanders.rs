@@ -1,11 +1,14 @@
+use crate::angulo_del_sol::AnguloDelSol;
 use crate::intervalos::Intervalo;
+use crate::limites::Limites;
+use crate::modelo_de_luz::ModeloDeLuz;
 use crate::parametros::Parametros;
+use crate::racional::Racional;
+use crate::silueta::Silueta;
+use crate::sombra::Sombra;
 
-//Rango válido para la posición X
-const RANGO_X: std::ops::RangeInclusive<u32> = 0..=300_000;
-
-/// Rango válido para la altura H
-const RANGO_H: std::ops::RangeInclusive<u32> = 1..=1000;
+/// Rango válido para la opacidad de un flatlander
+const RANGO_OPACIDAD: std::ops::RangeInclusive<f64> = 0.0..=1.0;
 
 /// Representa un flatlander (ser plano) con posición y longitud de sombra calculada.
 ///
@@ -15,7 +18,13 @@ const RANGO_H: std::ops::RangeInclusive<u32> = 1..=1000;
 /// # Campos
 ///
 /// * `x` - La posición del flatlander en el eje X
-/// * `l` - La longitud de la sombra
+/// * `h` - La altura del flatlander, o la de su parte más alta si flota; con silueta, la
+///   de su vértice más alto, que puede no ser entera
+/// * `y0` - La altura de su parte más baja, mayor a cero si flota sobre el camino
+/// * `w` - El ancho del flatlander, que ocupa el camino de `x` a `x + w`
+/// * `l` - La longitud de la sombra, negativa si la sombra cae hacia el oeste
+/// * `opacidad` - La fracción de luz que bloquea, entre 0 (transparente) y 1 (opaco)
+/// * `silueta` - La forma del flatlander, si no es un poste
 ///
 /// # Ejemplo
 ///
@@ -32,7 +41,12 @@ const RANGO_H: std::ops::RangeInclusive<u32> = 1..=1000;
 #[derive(Debug, PartialEq)]
 pub struct Flatlander {
     pub x: u32,
+    pub h: f64,
+    pub y0: u32,
+    pub w: u32,
     pub l: f64,
+    pub opacidad: f64,
+    pub silueta: Option<Silueta>,
 }
 
 /// Implementación de métodos para Flatlander
@@ -42,6 +56,8 @@ impl Flatlander {
     /// La longitud de la sombra se calcula usando la fórmula trigonométrica:
     /// L = H / tan(theta)
     ///
+    /// Ver `longitud_de_sombra` para los ángulos mayores o iguales a 90°.
+    ///
     /// # Argumentos
     ///
     /// * `x` - La posición del flatlander en el eje X
@@ -50,7 +66,8 @@ impl Flatlander {
     ///
     /// # Retorna
     ///
-    /// Un nuevo `Flatlander` con la posición especificada y la longitud de sombra calculada
+    /// Un nuevo `Flatlander` opaco y sin ancho, con la posición especificada y la longitud
+    /// de sombra calculada
     ///
     /// # Ejemplo
     ///
@@ -62,15 +79,482 @@ impl Flatlander {
     /// assert!((flatlander.l - 10.0).abs() < 0.001);
     /// ```
     pub fn new(x: u32, h: u32, theta: u32) -> Self {
-        // L = H / tan(theta)
-        let l = h as f64 / (std::f64::consts::PI / 180.0 * theta as f64).tan();
+        let l = longitud_de_sombra(h as f64, theta);
+
+        Self {
+            x,
+            h: h as f64,
+            y0: 0,
+            w: 0,
+            l,
+            opacidad: 1.0,
+            silueta: None,
+        }
+    }
+
+    /// Crea un flatlander como `new`, pero con el sol en un ángulo que puede no ser entero.
+    ///
+    /// # Argumentos
+    ///
+    /// * `x` - La posición del flatlander en el eje X
+    /// * `h` - La altura del flatlander
+    /// * `theta` - El ángulo del sol, en grados o como pendiente exacta
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::racional::Racional;
+    ///
+    /// // Con pendiente 4/3 la sombra de un poste de 8 mide 6
+    /// let pendiente = AnguloDelSol::Pendiente(Racional::new(4, 3).unwrap());
+    /// assert_eq!(Flatlander::con_angulo(0, 8, &pendiente).l, 6.0);
+    /// assert_eq!(Flatlander::con_angulo(0, 8, &AnguloDelSol::Grados(45.0)), Flatlander::new(0, 8, 45));
+    /// ```
+    pub fn con_angulo(x: u32, h: u32, theta: &AnguloDelSol) -> Self {
+        Self {
+            l: theta.longitud_de_sombra(h as f64),
+            ..Self::new(x, h, 90)
+        }
+    }
+
+    /// Crea un flatlander opaco con la forma de la silueta dada en lugar de un poste.
+    ///
+    /// La altura del flatlander es la de su vértice más alto, sin redondear, y la
+    /// longitud de su sombra la de ese vértice.
+    ///
+    /// # Argumentos
+    ///
+    /// * `x` - La posición de la base del flatlander en el eje X
+    /// * `silueta` - La forma del flatlander, relativa a su base
+    /// * `theta` - El ángulo del sol, en grados o como pendiente exacta
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+    /// use el_rompecabezas_de_las_sombreas::silueta::Silueta;
+    ///
+    /// // Un árbol con la copa de 6 de ancho entre los 4 y los 10 de altura
+    /// let copa = vec![(0.0, 0.0), (0.0, 4.0), (-3.0, 6.0), (0.0, 10.0), (3.0, 6.0)];
+    /// let arbol = Flatlander::con_silueta(20, Silueta::new(copa).unwrap(), &AnguloDelSol::Grados(45.0));
+    ///
+    /// assert_eq!(arbol.h, 10.0);
+    /// assert_eq!(arbol.sombra(), Intervalo { inicio: 20.0, fin: 30.0 });
+    /// ```
+    pub fn con_silueta(x: u32, silueta: Silueta, theta: &AnguloDelSol) -> Self {
+        let altura = silueta.altura();
+
+        Self {
+            x,
+            h: altura,
+            y0: 0,
+            w: 0,
+            l: theta.longitud_de_sombra(altura),
+            opacidad: 1.0,
+            silueta: Some(silueta),
+        }
+    }
+
+    /// Retorna el mismo flatlander con el ancho indicado.
+    ///
+    /// # Argumentos
+    ///
+    /// * `w` - El ancho del flatlander, que ocupa el camino de `x` a `x + w`
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+    ///
+    /// let casa = Flatlander::new(5, 10, 45).con_ancho(8);
+    /// assert_eq!(casa.sombra(), Intervalo { inicio: 5.0, fin: 23.0 });
+    /// ```
+    pub fn con_ancho(self, w: u32) -> Self {
+        Self { w, ..self }
+    }
 
-        Self { x, l }
+    /// Retorna el mismo flatlander flotando a la altura `y0` sobre el camino, como un
+    /// globo o un cartel colgante que ocupa las alturas de `y0` a `h`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `y0` - La altura de la parte más baja del flatlander
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::FueraDeRango` - Si `y0` es mayor que la altura del flatlander
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+    ///
+    /// let globo = Flatlander::new(10, 20, 45).con_elevacion(15).unwrap();
+    /// let sombra = globo.sombra();
+    /// assert!((sombra.inicio - 25.0).abs() < 1e-9);
+    /// assert!((sombra.fin - 30.0).abs() < 1e-9);
+    /// assert!(Flatlander::new(10, 20, 45).con_elevacion(21).is_err());
+    /// ```
+    pub fn con_elevacion(self, y0: u32) -> Result<Self, crate::ErrorTipo> {
+        if y0 as f64 > self.h {
+            return Err(crate::ErrorTipo::FueraDeRango);
+        }
+        Ok(Self { y0, ..self })
     }
+
+    /// Retorna el mismo flatlander con la opacidad indicada.
+    ///
+    /// # Argumentos
+    ///
+    /// * `opacidad` - La fracción de luz que bloquea, entre 0 y 1
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::FueraDeRango` - Si la opacidad no está entre 0 y 1
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    ///
+    /// let arbusto = Flatlander::new(5, 10, 45).con_opacidad(0.5).unwrap();
+    /// assert_eq!(arbusto.opacidad, 0.5);
+    /// assert!(Flatlander::new(5, 10, 45).con_opacidad(1.5).is_err());
+    /// ```
+    pub fn con_opacidad(self, opacidad: f64) -> Result<Self, crate::ErrorTipo> {
+        if !RANGO_OPACIDAD.contains(&opacidad) {
+            return Err(crate::ErrorTipo::FueraDeRango);
+        }
+        Ok(Self { opacidad, ..self })
+    }
+
+    /// Retorna el intervalo del eje X cubierto por la sombra del flatlander.
+    ///
+    /// La sombra va desde la posición del flatlander hasta su posición más la
+    /// longitud de su sombra, que si es negativa queda al oeste del flatlander.
+    /// El camino que ocupa el flatlander tampoco recibe sol, así que la sombra
+    /// siempre lo incluye: `[x, x + w + l]` hacia el este y `[x + l, x + w]` hacia
+    /// el oeste. Si el flatlander flota, la sombra empieza donde cae la sombra de su
+    /// parte más baja y no incluye el camino debajo de él. Si el flatlander tiene
+    /// silueta, la sombra es la de su polígono y el ancho y la elevación no se usan.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+    ///
+    /// let flatlander = Flatlander { x: 5, h: 10.0, y0: 0, w: 0, l: 10.0, opacidad: 1.0, silueta: None };
+    /// assert_eq!(flatlander.sombra(), Intervalo { inicio: 5.0, fin: 15.0 });
+    /// ```
+    pub fn sombra(&self) -> Intervalo {
+        self.sombra_con_longitud(self.l)
+    }
+
+    /// Retorna el intervalo cubierto por la sombra del flatlander con el sol en un
+    /// ángulo cualquiera, sin importar el ángulo con el que fue creado.
+    ///
+    /// El ángulo puede no ser entero, lo que permite buscar ángulos con métodos
+    /// numéricos. Fuera de `(0, 180)` la sombra es infinita.
+    ///
+    /// # Argumentos
+    ///
+    /// * `grados` - El ángulo de incidencia de la luz en grados
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    ///
+    /// let flatlander = Flatlander::new(0, 10, 45);
+    /// assert_eq!(flatlander.sombra_con_angulo(45.0), flatlander.sombra());
+    /// assert!(flatlander.sombra_con_angulo(22.5).fin > 24.0);
+    /// ```
+    pub fn sombra_con_angulo(&self, grados: f64) -> Intervalo {
+        self.sombra_con_longitud(longitud_con_angulo(self.h, grados))
+    }
+
+    /// Calcula la sombra como `sombra`, pero con la longitud de sombra `l` indicada.
+    fn sombra_con_longitud(&self, l: f64) -> Intervalo {
+        if let Some(silueta) = &self.silueta {
+            let corrimiento = if self.h > 0.0 { l / self.h } else { 0.0 };
+            return silueta.proyectar(self.x as f64, corrimiento);
+        }
+
+        // Desplazamiento de la sombra de la parte más baja, proporcional a su altura
+        let base = if self.h > 0.0 && self.y0 > 0 {
+            l * self.y0 as f64 / self.h
+        } else {
+            0.0
+        };
+
+        Intervalo {
+            inicio: self.x as f64 + base.min(l),
+            fin: self.x as f64 + self.w as f64 + base.max(l),
+        }
+    }
+
+    /// Retorna la sombra del flatlander con extremos racionales exactos.
+    ///
+    /// Con una pendiente racional, las sombras de flatlanders de posición, ancho y
+    /// alturas enteras tienen extremos racionales, igual que en `sombra`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `theta` - El ángulo del sol con el que se creó el flatlander
+    ///
+    /// # Retorna
+    ///
+    /// `None` si el ángulo no es una pendiente, el flatlander tiene silueta o una altura
+    /// que no es entera, o algún extremo no entra en un `Racional`.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::racional::Racional;
+    ///
+    /// let pendiente = AnguloDelSol::Pendiente(Racional::new(3, 1).unwrap());
+    /// let sombra = Flatlander::con_angulo(2, 10, &pendiente).sombra_exacta(&pendiente).unwrap();
+    /// assert_eq!(sombra.fin, Racional::new(16, 3).unwrap());
+    /// ```
+    pub fn sombra_exacta(&self, theta: &AnguloDelSol) -> Option<Intervalo<Racional>> {
+        if self.silueta.is_some() || self.h.fract() != 0.0 {
+            return None;
+        }
+        let l = theta.longitud_exacta(self.h as u32)?;
+        let base = theta.longitud_exacta(self.y0)?;
+        let x = Racional::entero(self.x as i128);
+        let fin_del_camino = x.checked_add(Racional::entero(self.w as i128))?;
+
+        Some(Intervalo {
+            inicio: x.checked_add(base.min(l))?,
+            fin: fin_del_camino.checked_add(base.max(l))?,
+        })
+    }
+
+    /// Retorna la umbra y la sombra completa (umbra más penumbra) del flatlander, con
+    /// un sol que no es un punto sino un disco de radio angular `radio_angular`.
+    ///
+    /// Un punto del camino está en la umbra si el flatlander le tapa todo el disco, y en
+    /// la penumbra si le tapa solo una parte. Cada extremo de la sombra se mueve siempre
+    /// hacia el mismo lado al subir el ángulo, así que alcanza con proyectar el cuerpo
+    /// completo, con su elevación o su silueta, desde los dos bordes del disco: la umbra
+    /// es lo que tapan ambos y la sombra completa lo que tapa alguno.
+    ///
+    /// # Argumentos
+    ///
+    /// * `theta` - El ángulo del centro del sol
+    /// * `radio_angular` - El radio angular del sol en grados
+    ///
+    /// # Retorna
+    ///
+    /// Una tupla `(umbra, sombra_completa)`, donde la umbra está contenida en la sombra
+    /// completa, o es `None` si a ningún punto le tapa todo el disco.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    ///
+    /// let theta = AnguloDelSol::Grados(45.0);
+    /// let (umbra, completa) = Flatlander::new(0, 10, 45).sombras_con_disco_solar(&theta, 0.27);
+    /// let umbra = umbra.unwrap();
+    /// assert!(umbra.fin < 10.0 && 10.0 < completa.fin);
+    /// assert_eq!((umbra.inicio, completa.inicio), (0.0, 0.0));
+    /// ```
+    pub fn sombras_con_disco_solar(
+        &self,
+        theta: &AnguloDelSol,
+        radio_angular: f64,
+    ) -> (Option<Intervalo>, Intervalo) {
+        let centro = theta.grados();
+        let oeste = self.sombra_con_angulo(centro - radio_angular);
+        let este = self.sombra_con_angulo(centro + radio_angular);
+
+        let umbra = Intervalo {
+            inicio: oeste.inicio.max(este.inicio),
+            fin: oeste.fin.min(este.fin),
+        };
+        let completa = Intervalo {
+            inicio: oeste.inicio.min(este.inicio),
+            fin: oeste.fin.max(este.fin),
+        };
+        ((umbra.inicio <= umbra.fin).then_some(umbra), completa)
+    }
+
+    /// Retorna la sombra que proyecta el flatlander bajo un modelo de luz dado.
+    ///
+    /// A diferencia de `sombra`, no usa la longitud calculada en `new` sino la altura
+    /// del flatlander, por lo que sirve para cualquier fuente de luz. Un flatlander con
+    /// ancho se trata como dos postes en sus bordes, más el camino que ocupa entre ellos,
+    /// y uno con silueta como un poste en cada vértice. Uno que flota proyecta solo la
+    /// sombra de sus cuatro esquinas.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+    /// use el_rompecabezas_de_las_sombreas::modelo_de_luz::ModeloDeLuz;
+    ///
+    /// let flatlander = Flatlander::new(10, 20, 45);
+    /// let farol = ModeloDeLuz::Lampara { x: 0.0, y: 15.0 };
+    /// assert!(!flatlander.sombra_con_luz(&farol).es_acotada());
+    /// ```
+    pub fn sombra_con_luz(&self, luz: &ModeloDeLuz) -> Sombra {
+        // Con el sol se usa la misma cuenta que `sombra`, para dar los mismos extremos
+        if let ModeloDeLuz::Sol { theta } = luz {
+            let sombra = self.sombra_con_longitud(theta.longitud_de_sombra(self.h));
+            return Sombra::desde_extremos(sombra.inicio, sombra.fin);
+        }
+
+        if let Some(silueta) = &self.silueta {
+            return proyectar_vertices(silueta, self.x as f64, luz);
+        }
+
+        if self.y0 > 0 {
+            return self.sombra_flotante_con_luz(luz);
+        }
+
+        let oeste = luz.proyectar(self.x as f64, self.h);
+        if self.w == 0 {
+            return oeste;
+        }
+
+        let este = luz.proyectar(self.x as f64 + self.w as f64, self.h);
+        oeste.abarcar(&este)
+    }
+
+    /// Proyecta las esquinas de un flatlander que flota y retorna la sombra que las abarca.
+    fn sombra_flotante_con_luz(&self, luz: &ModeloDeLuz) -> Sombra {
+        let (oeste, este) = (self.x as f64, self.x as f64 + self.w as f64);
+        let (abajo, arriba) = (self.y0 as f64, self.h);
+        let puntas = [
+            (oeste, abajo),
+            (oeste, arriba),
+            (este, abajo),
+            (este, arriba),
+        ]
+        .map(|(x, altura)| luz.punta(x, altura));
+
+        Sombra::desde_extremos(
+            puntas.iter().copied().fold(f64::INFINITY, f64::min),
+            puntas.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        )
+    }
+
+    /// Retorna los puntos `(x, altura sobre el suelo)` del contorno del flatlander: los
+    /// vértices de su silueta, o las cuatro esquinas de su rectángulo.
+    pub(crate) fn contorno(&self) -> Vec<(f64, f64)> {
+        let x = self.x as f64;
+        if let Some(silueta) = &self.silueta {
+            return silueta
+                .vertices()
+                .iter()
+                .map(|&(dx, dy)| (x + dx, dy))
+                .collect();
+        }
+
+        let este = x + self.w as f64;
+        let (abajo, arriba) = (self.y0 as f64, self.h);
+        vec![(x, abajo), (x, arriba), (este, abajo), (este, arriba)]
+    }
+}
+
+/// Proyecta cada vértice de una silueta con la luz dada y retorna la sombra que las abarca.
+fn proyectar_vertices(silueta: &Silueta, x: f64, luz: &ModeloDeLuz) -> Sombra {
+    silueta
+        .vertices()
+        .iter()
+        .map(|&(dx, dy)| luz.proyectar(x + dx, dy))
+        .reduce(|sombra, otra| sombra.abarcar(&otra))
+        .unwrap_or(Sombra::Acotada(Intervalo { inicio: x, fin: x }))
+}
+
+/// Calcula la longitud de la sombra de un poste de altura `h` con el sol a `theta` grados.
+///
+/// El ángulo se mide desde el horizonte oeste, así que hasta 90° el sol está al oeste y
+/// la sombra cae hacia el este. Pasados los 90° el sol está al este, la tangente es
+/// negativa y la longitud también, indicando que la sombra cae hacia el oeste. Con el
+/// sol justo encima (90°) la sombra mide exactamente cero.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::flatlanders::longitud_de_sombra;
+///
+/// assert!((longitud_de_sombra(10.0, 45) - 10.0).abs() < 1e-9);
+/// assert!((longitud_de_sombra(10.0, 135) + 10.0).abs() < 1e-9);
+/// assert_eq!(longitud_de_sombra(10.0, 90), 0.0);
+/// ```
+pub fn longitud_de_sombra(h: f64, theta: u32) -> f64 {
+    // tan(90°) no es infinita en punto flotante, así que el caso se resuelve aparte
+    if theta == 90 {
+        return 0.0;
+    }
+
+    // L = H / tan(theta)
+    h / (std::f64::consts::PI / 180.0 * theta as f64).tan()
+}
+
+/// Calcula la longitud de sombra de un poste de altura `h` para un ángulo en grados
+/// cualquiera. Fuera de `(0, 180)` el rayo no llega al suelo y la sombra es infinita.
+pub(crate) fn longitud_con_angulo(h: f64, grados: f64) -> f64 {
+    if h == 0.0 || grados == 90.0 {
+        0.0
+    } else if grados <= 0.0 {
+        f64::INFINITY
+    } else if grados >= 180.0 {
+        f64::NEG_INFINITY
+    } else {
+        h / grados.to_radians().tan()
+    }
+}
+
+/// Calcula una cota del error relativo de la longitud de sombra que calcula `Flatlander::new`.
+///
+/// Considera el redondeo al pasar `theta` a radianes, amplificado por la derivada de la
+/// tangente, el error de `tan` (que se asume menor a un ulp) y el de la división, con un
+/// factor de seguridad. Cerca de 0°, 90° y 180° la cota crece porque la sombra está
+/// mal condicionada respecto del ángulo.
+///
+/// # Argumentos
+///
+/// * `theta` - El ángulo de incidencia de la luz en grados
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::flatlanders::error_relativo_de_sombra;
+///
+/// assert!(error_relativo_de_sombra(45) < 1e-14);
+/// ```
+pub fn error_relativo_de_sombra(theta: u32) -> f64 {
+    error_relativo_con_angulo(theta as f64)
+}
+
+/// Calcula la misma cota que `error_relativo_de_sombra` para un ángulo en grados cualquiera.
+pub(crate) fn error_relativo_con_angulo(grados: f64) -> f64 {
+    // A 90° la longitud es exactamente cero, sin error
+    if grados == 90.0 {
+        return 0.0;
+    }
+
+    let radianes = std::f64::consts::PI / 180.0 * grados;
+    4.0 * f64::EPSILON * (1.0 + radianes / (radianes.sin() * radianes.cos()).abs())
 }
 
 /// Esta función lee exactamente `n` líneas de entrada, donde cada línea debe contener
-/// la posición X y la altura H del flatlander.
+/// la posición X y la altura H del flatlander, y opcionalmente su ancho W y la altura
+/// Y0 a la que flota (para dar Y0 sin ancho se usa W = 0).
 /// Valida valores con los rangos permitidos.
 ///
 /// # Argumentos
@@ -87,12 +571,32 @@ impl Flatlander {
 ///
 /// * `ErrorTipo::IO` - Error al leer una línea
 /// * `ErrorTipo::LineaFaltante` - No hay suficientes líneas de entrada
-/// * `ErrorTipo::ValorFaltante` - Una línea no tiene exactamente 2 valores
+/// * `ErrorTipo::ValorFaltante` - Una línea no tiene entre 2 y 4 valores
 /// * `ErrorTipo::NumeroInvalido` - Un valor no se puede parsear como número
-/// * `ErrorTipo::FueraDeRango` - Un valor está fuera del rango permitido
+/// * `ErrorTipo::FueraDeRango` - Un valor está fuera del rango permitido, o Y0 es mayor que H
 pub fn leer_flatlanders_desde_stdin<R: std::io::BufRead>(
     lineas: &mut std::io::Lines<R>,
     params: &Parametros,
+) -> Result<Vec<Flatlander>, crate::ErrorTipo> {
+    leer_flatlanders_desde_stdin_con_limites(lineas, params, &Limites::concurso())
+}
+
+/// Lee los flatlanders igual que `leer_flatlanders_desde_stdin`, pero validando la
+/// posición y la altura con los límites dados.
+///
+/// # Argumentos
+///
+/// * `lineas` - Un iterador mutable sobre las líneas de entrada
+/// * `params` - Los parámetros que contienen el número de flatlanders y el ángulo theta
+/// * `limites` - Los rangos válidos para `x`, `h` y `w`
+///
+/// # Errores
+///
+/// Los mismos que `leer_flatlanders_desde_stdin`.
+pub fn leer_flatlanders_desde_stdin_con_limites<R: std::io::BufRead>(
+    lineas: &mut std::io::Lines<R>,
+    params: &Parametros,
+    limites: &Limites,
 ) -> Result<Vec<Flatlander>, crate::ErrorTipo> {
     let mut flatlanders: Vec<Flatlander> = Vec::with_capacity(params.n as usize);
 
@@ -108,15 +612,15 @@ pub fn leer_flatlanders_desde_stdin<R: std::io::BufRead>(
         // Quitamos los espacios en blanco de la línea y guardamos los números en un vector de literales
         let tokens: Vec<&str> = linea.split_whitespace().collect();
 
-        // Si no tenemos 2 tokens o "supuestos numeros" (x y h), retornamos error
-        if tokens.len() != 2 {
+        // Si no tenemos entre 2 y 4 tokens o "supuestos numeros" (x, h, y w e y0 opcionales), retornamos error
+        if !(2..=4).contains(&tokens.len()) {
             return Err(crate::ErrorTipo::ValorFaltante);
         }
 
         // Si no se puede parsear x (o esta fuera de rango) retorno error
         let x: u32 = match tokens[0].trim().parse() {
             Ok(v) => {
-                if !RANGO_X.contains(&v) {
+                if !limites.x.contains(&v) {
                     return Err(crate::ErrorTipo::FueraDeRango);
                 }
                 v
@@ -127,7 +631,7 @@ pub fn leer_flatlanders_desde_stdin<R: std::io::BufRead>(
         // Si no se puede parsear h (o esta fuera de rango) retorno error
         let h: u32 = match tokens[1].trim().parse() {
             Ok(v) => {
-                if !RANGO_H.contains(&v) {
+                if !limites.h.contains(&v) {
                     return Err(crate::ErrorTipo::FueraDeRango);
                 }
                 v
@@ -135,8 +639,28 @@ pub fn leer_flatlanders_desde_stdin<R: std::io::BufRead>(
             Err(_) => return Err(crate::ErrorTipo::NumeroInvalido),
         };
 
+        // El ancho es opcional, para seguir aceptando el formato de dos columnas
+        let w: u32 = match tokens.get(2).map(|token| token.trim().parse()) {
+            None => 0,
+            Some(Ok(v)) => {
+                if !limites.w.contains(&v) {
+                    return Err(crate::ErrorTipo::FueraDeRango);
+                }
+                v
+            }
+            Some(Err(_)) => return Err(crate::ErrorTipo::NumeroInvalido),
+        };
+
+        // La elevación también es opcional, y no puede superar la altura
+        let y0: u32 = match tokens.get(3).map(|token| token.trim().parse()) {
+            None => 0,
+            Some(Ok(v)) => v,
+            Some(Err(_)) => return Err(crate::ErrorTipo::NumeroInvalido),
+        };
+
         //Guardamos en el final del vector al flatlander i
-        flatlanders.push(Flatlander::new(x, h, params.theta));
+        let flatlander = Flatlander::con_angulo(x, h, &params.theta).con_ancho(w);
+        flatlanders.push(flatlander.con_elevacion(y0)?);
     }
 
     //Retornamos la colección de flatlanders
@@ -145,48 +669,79 @@ pub fn leer_flatlanders_desde_stdin<R: std::io::BufRead>(
 
 /// Crea una lista de intervalos de sombra a partir de una lista de flatlanders.
 ///
-/// Cada flatlander proyecta una sombra que cubre un intervalo en el eje X desde
-/// su posición hasta su posición más la longitud de su sombra.
+/// Cada flatlander proyecta una sombra bajo el modelo de luz dado, calculada con
+/// `Flatlander::sombra_con_luz`. Las sombras de los flatlanders que flotan pueden quedar
+/// separadas de su posición, dejando huecos al sol que `fusionar_intervalos` respeta.
+/// Como una sombra sin fin no tiene un área finita, si alguna no es acotada se informan
+/// cuáles en lugar de retornar intervalos.
 ///
 /// # Argumentos
 ///
 /// * `flatlanders` - Una slice de flatlanders
+/// * `luz` - El modelo de la fuente de luz
 ///
 /// # Retorna
 ///
-/// Un `Vec<Interval>` donde cada intervalo representa la sombra de un flatlander
+/// * `Ok(Vec<Intervalo>)` - Un intervalo por flatlander, en el mismo orden
+/// * `Err(Vec<usize>)` - Los índices de los flatlanders cuya sombra no es acotada
 ///
 /// # Ejemplo
 ///
 /// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
 /// use el_rompecabezas_de_las_sombreas::flatlanders::{Flatlander, crear_intervalo_de_flatlanders};
+/// use el_rompecabezas_de_las_sombreas::intervalos::calcular_area;
+/// use el_rompecabezas_de_las_sombreas::modelo_de_luz::ModeloDeLuz;
 ///
 /// let flatlanders = vec![
-///     Flatlander::new(0, 10, 45),  // Sombra de [0, 10]
-///     Flatlander::new(5, 10, 45),  // Sombra de [5, 15]
+///     Flatlander::new(0, 10, 45),                            // Sombra de [0, 10]
+///     Flatlander::new(0, 30, 45).con_elevacion(20).unwrap(), // Sombra de [20, 30]
 /// ];
+/// let sol = ModeloDeLuz::Sol { theta: AnguloDelSol::Grados(45.0) };
 ///
-/// let intervalos = crear_intervalo_de_flatlanders(&flatlanders);
-/// assert_eq!(intervalos.len(), 2);
+/// let mut intervalos = crear_intervalo_de_flatlanders(&flatlanders, &sol).unwrap();
+/// assert!((calcular_area(&mut intervalos) - 20.0).abs() < 1e-9);
 /// ```
-pub fn crear_intervalo_de_flatlanders(flatlanders: &[Flatlander]) -> Vec<Intervalo> {
-    flatlanders
-        .iter()
-        .map(|f| Intervalo {
-            inicio: f.x as f64,
-            fin: f.x as f64 + f.l,
-        })
-        .collect()
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::flatlanders::{Flatlander, crear_intervalo_de_flatlanders};
+/// use el_rompecabezas_de_las_sombreas::modelo_de_luz::ModeloDeLuz;
+///
+/// let flatlanders = vec![
+///     Flatlander::new(10, 5, 45),
+///     Flatlander::new(20, 30, 45),
+/// ];
+/// let farol = ModeloDeLuz::Lampara { x: 0.0, y: 20.0 };
+///
+/// let resultado = crear_intervalo_de_flatlanders(&flatlanders, &farol);
+/// assert_eq!(resultado, Err(vec![1]));
+/// ```
+pub fn crear_intervalo_de_flatlanders(
+    flatlanders: &[Flatlander],
+    luz: &ModeloDeLuz,
+) -> Result<Vec<Intervalo>, Vec<usize>> {
+    let sombras: Vec<Sombra> = flatlanders.iter().map(|f| f.sombra_con_luz(luz)).collect();
+
+    let no_acotadas: Vec<usize> = (0..sombras.len())
+        .filter(|&i| !sombras[i].es_acotada())
+        .collect();
+    if !no_acotadas.is_empty() {
+        return Err(no_acotadas);
+    }
+
+    Ok(sombras.iter().map(Sombra::como_intervalo).collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::intervalos::calcular_area;
     use std::io::BufRead;
 
     fn assert_flatlander_eq(actual: &Flatlander, esperado: &Flatlander) {
         assert_eq!(actual.x, esperado.x);
+        assert_eq!(actual.w, esperado.w);
         assert!((actual.l - esperado.l).abs() < PRECISION_IGUALDAD_FLOAT);
     }
 
@@ -198,7 +753,15 @@ mod tests {
     #[test]
     fn test_comparacion_de_flatlanders_iguales() {
         let flatlander = Flatlander::new(10, 20, 45);
-        let esperado = Flatlander { x: 10, l: 20.0 };
+        let esperado = Flatlander {
+            x: 10,
+            h: 20.0,
+            y0: 0,
+            w: 0,
+            l: 20.0,
+            opacidad: 1.0,
+            silueta: None,
+        };
 
         assert_flatlander_eq(&flatlander, &esperado);
     }
@@ -211,8 +774,11 @@ mod tests {
             Flatlander::new(50, 150, 30),
             Flatlander::new(100, 200, 30),
         ];
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(30.0),
+        };
 
-        let intervalos = crear_intervalo_de_flatlanders(&flatlanders);
+        let intervalos = crear_intervalo_de_flatlanders(&flatlanders, &sol).unwrap();
         let intervalos_esperado = [
             Intervalo {
                 inicio: 0.0,
@@ -232,13 +798,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_error_relativo_de_sombra_acota_el_error() {
+        // tan(45°) = 1, por lo que la sombra exacta es igual a la altura
+        let flatlander = Flatlander::new(0, 1000, 45);
+        let error = (flatlander.l - 1000.0).abs() / 1000.0;
+        assert!(error <= error_relativo_de_sombra(45));
+        assert!(error_relativo_de_sombra(80) > error_relativo_de_sombra(45));
+    }
+
+    #[test]
+    fn test_intervalos_con_lampara() {
+        let flatlanders = vec![Flatlander::new(10, 5, 45), Flatlander::new(30, 5, 45)];
+        let farol = ModeloDeLuz::Lampara { x: 20.0, y: 10.0 };
+
+        let intervalos = crear_intervalo_de_flatlanders(&flatlanders, &farol).unwrap();
+        assert_intervalo_eq(
+            &intervalos[0],
+            &Intervalo {
+                inicio: 0.0,
+                fin: 10.0,
+            },
+        );
+        assert_intervalo_eq(
+            &intervalos[1],
+            &Intervalo {
+                inicio: 30.0,
+                fin: 40.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_intervalos_con_sol_coinciden_con_los_originales() {
+        let flatlanders = vec![Flatlander::new(0, 100, 30), Flatlander::new(50, 150, 30)];
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(30.0),
+        };
+
+        let con_luz = crear_intervalo_de_flatlanders(&flatlanders, &sol);
+        let originales = flatlanders.iter().map(Flatlander::sombra).collect();
+        assert_eq!(con_luz, Ok(originales));
+    }
+
+    #[test]
+    fn test_sombra_hacia_el_oeste() {
+        let flatlander = Flatlander::new(100, 10, 135);
+
+        assert!(flatlander.l < 0.0);
+        assert_intervalo_eq(
+            &flatlander.sombra(),
+            &Intervalo {
+                inicio: 90.0,
+                fin: 100.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_sombra_con_sol_vertical() {
+        let flatlander = Flatlander::new(100, 10, 90);
+        assert_eq!(
+            flatlander.sombra(),
+            Intervalo {
+                inicio: 100.0,
+                fin: 100.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_sombras_simetricas_respecto_de_90() {
+        for theta in 1..90 {
+            let este = Flatlander::new(50, 30, theta).sombra();
+            let oeste = Flatlander::new(50, 30, 180 - theta).sombra();
+            assert!(((este.fin - 50.0) - (50.0 - oeste.inicio)).abs() < 1e-6);
+        }
+    }
+
     #[test]
     fn test_leer_flatlanders_desde_stdin_correcto() {
         let input = "10 20\n5 15\n";
         let reader = std::io::BufReader::new(input.as_bytes());
         let mut lineas = reader.lines();
 
-        let params = Parametros { theta: 45, n: 2 };
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 2,
+        };
         let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
 
         assert!(resultado.is_ok());
@@ -248,13 +895,302 @@ mod tests {
         assert_flatlander_eq(&flatlanders[1], &Flatlander::new(5, 15, 45));
     }
 
+    #[test]
+    fn test_leer_flatlanders_con_ancho_opcional() {
+        let input = "10 20 5\n5 15\n";
+        let reader = std::io::BufReader::new(input.as_bytes());
+        let mut lineas = reader.lines();
+
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 2,
+        };
+        let flatlanders = leer_flatlanders_desde_stdin(&mut lineas, &params).unwrap();
+
+        assert_flatlander_eq(&flatlanders[0], &Flatlander::new(10, 20, 45).con_ancho(5));
+        assert_flatlander_eq(&flatlanders[1], &Flatlander::new(5, 15, 45));
+    }
+
+    #[test]
+    fn test_leer_flatlanders_demasiados_valores() {
+        let input = "10 20 5 1 7\n";
+        let reader = std::io::BufReader::new(input.as_bytes());
+        let mut lineas = reader.lines();
+
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 1,
+        };
+        let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
+
+        assert_eq!(resultado, Err(crate::ErrorTipo::ValorFaltante));
+    }
+
+    #[test]
+    fn test_sombra_con_ancho() {
+        let este = Flatlander::new(10, 10, 45).con_ancho(4);
+        let oeste = Flatlander::new(10, 10, 135).con_ancho(4);
+        let vertical = Flatlander::new(10, 10, 90).con_ancho(4);
+
+        assert_intervalo_eq(
+            &este.sombra(),
+            &Intervalo {
+                inicio: 10.0,
+                fin: 24.0,
+            },
+        );
+        assert_intervalo_eq(
+            &oeste.sombra(),
+            &Intervalo {
+                inicio: 0.0,
+                fin: 14.0,
+            },
+        );
+        assert_intervalo_eq(
+            &vertical.sombra(),
+            &Intervalo {
+                inicio: 10.0,
+                fin: 14.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_sombra_con_ancho_y_lampara() {
+        let casa = Flatlander::new(10, 4, 45).con_ancho(10);
+
+        // Lámpara al oeste: la sombra empieza en la casa y sigue detrás del borde este
+        let farol = ModeloDeLuz::Lampara { x: 0.0, y: 8.0 };
+        let sombra = casa.sombra_con_luz(&farol).como_intervalo();
+        assert_intervalo_eq(
+            &sombra,
+            &Intervalo {
+                inicio: 10.0,
+                fin: 40.0,
+            },
+        );
+
+        // Lámpara encima de la casa y más baja que ella: no llega luz a ningún lado
+        let farol_bajo = ModeloDeLuz::Lampara { x: 15.0, y: 3.0 };
+        assert_eq!(casa.sombra_con_luz(&farol_bajo), Sombra::Completa);
+    }
+
+    #[test]
+    fn test_silueta_en_calcular_area() {
+        // Carpa de 4 de ancho y 4 de alto: sombra de [10, 16] con 45° y [8, 14] con 135°
+        let carpa = || Silueta::new(vec![(0.0, 0.0), (2.0, 4.0), (4.0, 0.0)]).unwrap();
+        let flatlanders = [
+            Flatlander::con_silueta(10, carpa(), &AnguloDelSol::Grados(45.0)),
+            Flatlander::con_silueta(10, carpa(), &AnguloDelSol::Grados(135.0)),
+            Flatlander::new(20, 5, 45),
+        ];
+
+        let mut sombras: Vec<Intervalo> = flatlanders.iter().map(Flatlander::sombra).collect();
+        let area = calcular_area(&mut sombras);
+        assert!((area - 13.0).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_silueta_con_luz_coincide_con_sombra() {
+        let silueta = Silueta::new(vec![(0.0, 0.0), (0.0, 3.0), (6.0, 1.0), (6.0, 0.0)]).unwrap();
+        let theta = AnguloDelSol::Grados(30.0);
+        let casa = Flatlander::con_silueta(10, silueta, &theta);
+        let sol = ModeloDeLuz::Sol { theta };
+
+        assert_intervalo_eq(&casa.sombra_con_luz(&sol).como_intervalo(), &casa.sombra());
+    }
+
+    #[test]
+    fn test_silueta_con_altura_no_entera() {
+        // Un poste de 10.5 de alto como silueta: la altura guardada no se redondea
+        let poste = Silueta::new(vec![(0.0, 0.0), (0.0, 10.5)]).unwrap();
+        let flatlander = Flatlander::con_silueta(0, poste, &AnguloDelSol::Grados(45.0));
+
+        assert_eq!(flatlander.h, 10.5);
+        assert!((flatlander.h / flatlander.l - 1.0).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert_intervalo_eq(
+            &flatlander.sombra_con_angulo(30.0),
+            &Intervalo {
+                inicio: 0.0,
+                fin: 10.5 * 3f64.sqrt(),
+            },
+        );
+        assert_eq!(flatlander.sombra_exacta(&AnguloDelSol::Grados(45.0)), None);
+    }
+
+    #[test]
+    fn test_leer_flatlanders_con_elevacion() {
+        let input = "10 20 0 5\n5 15 2\n0 4 0 5\n";
+        let reader = std::io::BufReader::new(input.as_bytes());
+        let mut lineas = reader.lines();
+
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 3,
+        };
+        let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
+        assert_eq!(resultado, Err(crate::ErrorTipo::FueraDeRango));
+
+        let reader = std::io::BufReader::new("10 20 0 5\n".as_bytes());
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 1,
+        };
+        let flatlanders = leer_flatlanders_desde_stdin(&mut reader.lines(), &params).unwrap();
+        assert_eq!(flatlanders[0].y0, 5);
+    }
+
+    #[test]
+    fn test_sombra_flotante() {
+        // Cartel de ancho 4 entre las alturas 6 y 10
+        let este = Flatlander::new(10, 10, 45)
+            .con_ancho(4)
+            .con_elevacion(6)
+            .unwrap();
+        let oeste = Flatlander::new(10, 10, 135)
+            .con_ancho(4)
+            .con_elevacion(6)
+            .unwrap();
+
+        assert_intervalo_eq(
+            &este.sombra(),
+            &Intervalo {
+                inicio: 16.0,
+                fin: 24.0,
+            },
+        );
+        assert_intervalo_eq(
+            &oeste.sombra(),
+            &Intervalo {
+                inicio: 0.0,
+                fin: 8.0,
+            },
+        );
+
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(135.0),
+        };
+        assert_intervalo_eq(
+            &oeste.sombra_con_luz(&sol).como_intervalo(),
+            &oeste.sombra(),
+        );
+    }
+
+    #[test]
+    fn test_sombra_flotante_con_lampara() {
+        // Globo en x=10 entre las alturas 2 y 4, con una lámpara a altura 8 en x=0
+        let globo = Flatlander::new(10, 4, 45).con_elevacion(2).unwrap();
+        let farol = ModeloDeLuz::Lampara { x: 0.0, y: 8.0 };
+
+        // Las puntas caen en 10 * 8 / 6 y 10 * 8 / 4
+        let sombra = globo.sombra_con_luz(&farol).como_intervalo();
+        assert_intervalo_eq(
+            &sombra,
+            &Intervalo {
+                inicio: 80.0 / 6.0,
+                fin: 20.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_disco_solar_de_radio_cero() {
+        let flatlander = Flatlander::new(10, 20, 30).con_ancho(5);
+        let theta = AnguloDelSol::Grados(30.0);
+        let (umbra, completa) = flatlander.sombras_con_disco_solar(&theta, 0.0);
+
+        assert_intervalo_eq(&umbra.unwrap(), &flatlander.sombra());
+        assert_intervalo_eq(&completa, &flatlander.sombra());
+    }
+
+    #[test]
+    fn test_disco_solar_sobre_la_vertical() {
+        // Con el sol a 90° y radio 45°, los bordes están a 45° y a 135°
+        let theta = AnguloDelSol::Grados(90.0);
+        let (umbra, completa) = Flatlander::new(10, 10, 90).sombras_con_disco_solar(&theta, 45.0);
+
+        assert_intervalo_eq(
+            &umbra.unwrap(),
+            &Intervalo {
+                inicio: 10.0,
+                fin: 10.0,
+            },
+        );
+        assert_intervalo_eq(
+            &completa,
+            &Intervalo {
+                inicio: 0.0,
+                fin: 20.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_disco_solar_hacia_el_oeste() {
+        // Centro a 135°, bordes a 120° y 150°
+        let theta = AnguloDelSol::Grados(135.0);
+        let (umbra, completa) = Flatlander::new(10, 10, 135).sombras_con_disco_solar(&theta, 15.0);
+        let corta = 10.0 / 60.0_f64.to_radians().tan();
+        let larga = 10.0 / 30.0_f64.to_radians().tan();
+
+        assert_intervalo_eq(
+            &umbra.unwrap(),
+            &Intervalo {
+                inicio: 10.0 - corta,
+                fin: 10.0,
+            },
+        );
+        assert_intervalo_eq(
+            &completa,
+            &Intervalo {
+                inicio: 10.0 - larga,
+                fin: 10.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_disco_solar_con_elevacion() {
+        // Flota entre 10 y 20: la sombra con el sol puntual es [10, 20] y la umbra queda
+        // dentro, entre los rayos de los bordes del disco
+        let theta = AnguloDelSol::Grados(45.0);
+        let globo = Flatlander::new(0, 20, 45).con_elevacion(10).unwrap();
+        let (umbra, completa) = globo.sombras_con_disco_solar(&theta, 0.27);
+        let umbra = umbra.unwrap();
+
+        assert!(10.0 < umbra.inicio && umbra.fin < 20.0);
+        assert!(completa.inicio < 10.0 && 20.0 < completa.fin);
+        assert!(umbra.fin - umbra.inicio < 10.0);
+
+        // Con un disco grande el hueco bajo el globo no queda nunca tapado del todo
+        let (umbra, _) = Flatlander::new(0, 2, 45)
+            .con_elevacion(1)
+            .unwrap()
+            .sombras_con_disco_solar(&theta, 30.0);
+        assert_eq!(umbra, None);
+    }
+
+    #[test]
+    fn test_disco_solar_con_silueta() {
+        let carpa = Silueta::new(vec![(0.0, 0.0), (2.0, 4.0), (4.0, 0.0)]).unwrap();
+        let theta = AnguloDelSol::Grados(45.0);
+        let carpa = Flatlander::con_silueta(10, carpa, &theta);
+
+        let (umbra, completa) = carpa.sombras_con_disco_solar(&theta, 0.0);
+        assert_intervalo_eq(&umbra.unwrap(), &carpa.sombra());
+        assert_intervalo_eq(&completa, &carpa.sombra());
+    }
+
     #[test]
     fn test_leer_flatlanders_stdin_valor_faltante() {
         let input = "10\n5 15\n";
         let reader = std::io::BufReader::new(input.as_bytes());
         let mut lineas = reader.lines();
 
-        let params = Parametros { theta: 45, n: 2 };
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 2,
+        };
         let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
 
         assert_eq!(resultado, Err(crate::ErrorTipo::ValorFaltante));
@@ -266,7 +1202,10 @@ mod tests {
         let reader = std::io::BufReader::new(input.as_bytes());
         let mut lineas = reader.lines();
 
-        let params = Parametros { theta: 45, n: 2 };
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 2,
+        };
         let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
 
         assert_eq!(resultado, Err(crate::ErrorTipo::NumeroInvalido));
@@ -278,7 +1217,10 @@ mod tests {
         let reader = std::io::BufReader::new(input.as_bytes());
         let mut lineas = reader.lines();
 
-        let params = Parametros { theta: 45, n: 2 };
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 2,
+        };
         let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
 
         assert_eq!(resultado, Err(crate::ErrorTipo::LineaFaltante));
@@ -290,7 +1232,10 @@ mod tests {
         let reader = std::io::BufReader::new(input.as_bytes());
         let mut lineas = reader.lines();
 
-        let params = Parametros { theta: 45, n: 1 };
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 1,
+        };
         let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
 
         assert_eq!(resultado, Err(crate::ErrorTipo::FueraDeRango));
@@ -302,7 +1247,10 @@ mod tests {
         let reader = std::io::BufReader::new(input.as_bytes());
         let mut lineas = reader.lines();
 
-        let params = Parametros { theta: 45, n: 1 };
+        let params = Parametros {
+            theta: AnguloDelSol::Grados(45.0),
+            n: 1,
+        };
         let resultado = leer_flatlanders_desde_stdin(&mut lineas, &params);
 
         assert_eq!(resultado, Err(crate::ErrorTipo::FueraDeRango));
@@ -0,0 +1,183 @@
+use crate::intervalos::Intervalo;
+
+/// Histograma de la profundidad de cobertura de un conjunto de sombras.
+///
+/// Para cada `k` guarda la longitud del camino cubierta por exactamente `k` sombras.
+/// La posición `0` corresponde a los huecos iluminados que quedan entre el inicio de
+/// la primera sombra y el fin de la última.
+///
+/// # Campos
+///
+/// * `longitudes` - `longitudes[k]` es la longitud cubierta por exactamente `k` sombras
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::histograma_de_cobertura::HistogramaDeCobertura;
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+///
+/// let mut intervalos = vec![
+///     Intervalo { inicio: 0.0, fin: 10.0 },
+///     Intervalo { inicio: 5.0, fin: 15.0 },
+/// ];
+///
+/// let histograma = HistogramaDeCobertura::new(&mut intervalos);
+/// assert_eq!(histograma.exactamente(1), 10.0); // [0, 5] y [10, 15]
+/// assert_eq!(histograma.exactamente(2), 5.0); // [5, 10]
+/// assert_eq!(histograma.al_menos(1), 15.0);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct HistogramaDeCobertura {
+    longitudes: Vec<f64>,
+}
+
+impl HistogramaDeCobertura {
+    /// Calcula el histograma barriendo los extremos de los intervalos en orden.
+    ///
+    /// Igual que `calcular_area`, ordena in place los intervalos por su inicio. Los
+    /// fines se ordenan aparte y ambas secuencias se recorren a la vez.
+    ///
+    /// # Argumentos
+    ///
+    /// * `intervalos` - El slice mutable de intervalos, con `inicio <= fin`
+    pub fn new(intervalos: &mut [Intervalo]) -> Self {
+        intervalos.sort_by(|a, b| a.inicio.total_cmp(&b.inicio));
+
+        let mut fines: Vec<f64> = intervalos.iter().map(|i| i.fin).collect();
+        fines.sort_by(|a, b| a.total_cmp(b));
+
+        let mut histograma = Self {
+            longitudes: Vec::new(),
+        };
+        histograma.barrer(intervalos, &fines);
+        histograma
+    }
+
+    /// Recorre los inicios y fines ordenados acumulando la longitud de cada tramo
+    /// en la profundidad que tiene.
+    fn barrer(&mut self, intervalos: &[Intervalo], fines: &[f64]) {
+        let (mut i, mut j, mut profundidad) = (0, 0, 0);
+        let mut anterior = intervalos.first().map_or(0.0, |primero| primero.inicio);
+
+        while j < fines.len() {
+            // Ante un empate procesamos primero los inicios, para no cerrar un
+            // intervalo antes de haberlo abierto
+            let es_inicio = i < intervalos.len() && intervalos[i].inicio <= fines[j];
+            let posicion = if es_inicio {
+                intervalos[i].inicio
+            } else {
+                fines[j]
+            };
+
+            self.acumular(profundidad, posicion - anterior);
+            anterior = posicion;
+
+            if es_inicio {
+                profundidad += 1;
+                i += 1;
+            } else {
+                profundidad -= 1;
+                j += 1;
+            }
+        }
+    }
+
+    /// Suma una longitud a la profundidad indicada, agrandando el histograma si hace falta.
+    fn acumular(&mut self, profundidad: usize, longitud: f64) {
+        if self.longitudes.len() <= profundidad {
+            self.longitudes.resize(profundidad + 1, 0.0);
+        }
+        self.longitudes[profundidad] += longitud;
+    }
+
+    /// Retorna la longitud cubierta por exactamente `k` sombras.
+    pub fn exactamente(&self, k: usize) -> f64 {
+        self.longitudes.get(k).copied().unwrap_or(0.0)
+    }
+
+    /// Retorna la longitud cubierta por al menos `k` sombras.
+    ///
+    /// Con `k = 1` coincide con el resultado de `calcular_area`.
+    pub fn al_menos(&self, k: usize) -> f64 {
+        self.longitudes.iter().skip(k).sum()
+    }
+
+    /// Retorna la mayor cantidad de sombras superpuestas en algún tramo de longitud positiva.
+    pub fn profundidad_maxima(&self) -> usize {
+        self.longitudes
+            .iter()
+            .rposition(|&longitud| longitud > 0.0)
+            .unwrap_or(0)
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intervalos::calcular_area;
+
+    fn intervalos_de(pares: &[(f64, f64)]) -> Vec<Intervalo> {
+        pares
+            .iter()
+            .map(|&(inicio, fin)| Intervalo { inicio, fin })
+            .collect()
+    }
+
+    #[test]
+    fn test_histograma_vacio() {
+        let histograma = HistogramaDeCobertura::new(&mut []);
+        assert_eq!(histograma.exactamente(0), 0.0);
+        assert_eq!(histograma.al_menos(1), 0.0);
+        assert_eq!(histograma.profundidad_maxima(), 0);
+    }
+
+    #[test]
+    fn test_histograma_con_huecos() {
+        let mut intervalos = intervalos_de(&[(10.0, 15.0), (0.0, 5.0)]);
+        let histograma = HistogramaDeCobertura::new(&mut intervalos);
+
+        assert_eq!(histograma.exactamente(0), 5.0);
+        assert_eq!(histograma.exactamente(1), 10.0);
+        assert_eq!(histograma.al_menos(0), 15.0);
+        assert_eq!(histograma.profundidad_maxima(), 1);
+    }
+
+    #[test]
+    fn test_histograma_intervalos_anidados() {
+        let mut intervalos = intervalos_de(&[(0.0, 20.0), (5.0, 15.0), (8.0, 12.0)]);
+        let histograma = HistogramaDeCobertura::new(&mut intervalos);
+
+        assert_eq!(histograma.exactamente(1), 10.0);
+        assert_eq!(histograma.exactamente(2), 6.0);
+        assert_eq!(histograma.exactamente(3), 4.0);
+        assert_eq!(histograma.exactamente(4), 0.0);
+        assert_eq!(histograma.al_menos(2), 10.0);
+        assert_eq!(histograma.profundidad_maxima(), 3);
+    }
+
+    #[test]
+    fn test_histograma_intervalos_que_se_tocan_y_puntos() {
+        let mut intervalos = intervalos_de(&[(0.0, 5.0), (5.0, 10.0), (5.0, 5.0)]);
+        let histograma = HistogramaDeCobertura::new(&mut intervalos);
+
+        assert_eq!(histograma.exactamente(1), 10.0);
+        assert_eq!(histograma.profundidad_maxima(), 1);
+    }
+
+    #[test]
+    fn test_al_menos_uno_coincide_con_calcular_area() {
+        let pares = [(0.0, 5.0), (3.0, 8.0), (10.0, 15.0), (12.0, 18.0)];
+        let histograma = HistogramaDeCobertura::new(&mut intervalos_de(&pares));
+
+        assert_eq!(
+            histograma.al_menos(1),
+            calcular_area(&mut intervalos_de(&pares))
+        );
+    }
+}
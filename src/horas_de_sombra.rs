@@ -0,0 +1,189 @@
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{Intervalo, fusionar_intervalos};
+use crate::trayectoria_solar::TrayectoriaSolar;
+
+/// Cantidad de horas que cada punto del camino pasa a la sombra durante el día.
+///
+/// Es una función constante por tramos: fuera de los tramos guardados el punto está al
+/// sol todo el día.
+///
+/// # Campos
+///
+/// * `tramos` - Los tramos con sombra alguna vez, ordenados y disjuntos, junto con sus horas de sombra
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::horas_de_sombra::HorasDeSombra;
+/// use el_rompecabezas_de_las_sombreas::trayectoria_solar::TrayectoriaSolar;
+///
+/// // Dos horas con el sol a 45° al oeste y dos horas a 45° al este
+/// let trayectoria =
+///     TrayectoriaSolar::desde_perfil(0.0, 4.0, 2, |hora| if hora < 2.0 { 135.0 } else { 45.0 })
+///         .unwrap();
+/// let flatlanders = vec![Flatlander::new(0, 10, 45)];
+///
+/// let horas = HorasDeSombra::new(&flatlanders, &trayectoria);
+/// // [-10, 0] a la mañana y [0, 10] a la tarde, dos horas cada uno
+/// assert!((horas.total() - 40.0).abs() < 1e-9);
+/// assert!((horas.duracion_en(5.0) - 2.0).abs() < 1e-9);
+/// assert_eq!(horas.duracion_en(20.0), 0.0);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct HorasDeSombra {
+    tramos: Vec<(Intervalo, f64)>,
+}
+
+impl HorasDeSombra {
+    /// Calcula las horas de sombra sumando, paso a paso, la unión de las sombras.
+    ///
+    /// La sombra de cada flatlander se recalcula en cada paso con el ángulo que tiene el
+    /// sol en ese paso, sin usar la longitud calculada al crearlo.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders parados sobre el camino
+    /// * `trayectoria` - El recorrido del sol durante el día
+    pub fn new(flatlanders: &[Flatlander], trayectoria: &TrayectoriaSolar) -> Self {
+        let duracion = trayectoria.duracion_del_paso();
+
+        // Cada evento es (posición, si empieza o termina un tramo de sombra)
+        let mut eventos: Vec<(f64, bool)> = Vec::new();
+        for &theta in trayectoria.thetas() {
+            let mut sombras: Vec<Intervalo> = flatlanders
+                .iter()
+                .map(|f| f.sombra_con_angulo(theta))
+                .collect();
+            for tramo in fusionar_intervalos(&mut sombras) {
+                eventos.push((tramo.inicio, true));
+                eventos.push((tramo.fin, false));
+            }
+        }
+        eventos.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut horas = Self { tramos: Vec::new() };
+        horas.barrer(&eventos, duracion);
+        horas
+    }
+
+    /// Recorre los eventos contando en cuántos pasos está en sombra cada tramo.
+    ///
+    /// Se cuentan pasos en lugar de sumar horas para no acumular errores de redondeo.
+    fn barrer(&mut self, eventos: &[(f64, bool)], duracion: f64) {
+        let mut activos = 0_u32;
+        let mut anterior = eventos.first().map_or(0.0, |evento| evento.0);
+
+        for &(posicion, es_inicio) in eventos {
+            if posicion > anterior && activos > 0 {
+                let tramo = Intervalo {
+                    inicio: anterior,
+                    fin: posicion,
+                };
+                self.tramos.push((tramo, activos as f64 * duracion));
+            }
+            anterior = posicion;
+            activos = if es_inicio { activos + 1 } else { activos - 1 };
+        }
+    }
+
+    /// Retorna los tramos con sombra alguna vez junto con sus horas de sombra.
+    pub fn tramos(&self) -> &[(Intervalo, f64)] {
+        &self.tramos
+    }
+
+    /// Retorna las horas de sombra en el punto `x` del camino.
+    ///
+    /// En un extremo compartido por dos tramos se usa el tramo de la derecha.
+    pub fn duracion_en(&self, x: f64) -> f64 {
+        let posicion = self.tramos.partition_point(|(tramo, _)| tramo.fin <= x);
+        self.tramos
+            .get(posicion)
+            .filter(|(tramo, _)| tramo.inicio <= x)
+            .map_or(0.0, |(_, horas)| *horas)
+    }
+
+    /// Retorna el total de horas de sombra integrado a lo largo del camino.
+    pub fn total(&self) -> f64 {
+        self.tramos
+            .iter()
+            .map(|(tramo, horas)| (tramo.fin - tramo.inicio) * horas)
+            .sum()
+    }
+
+    /// Ordena los lugares dados de más a menos horas de sombra.
+    ///
+    /// # Argumentos
+    ///
+    /// * `lugares` - Las posiciones de los lugares a comparar
+    ///
+    /// # Retorna
+    ///
+    /// Los índices de los lugares, del más sombreado al menos sombreado. Los empates
+    /// conservan el orden original.
+    pub fn ordenar_lugares(&self, lugares: &[f64]) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..lugares.len()).collect();
+        indices.sort_by(|&a, &b| {
+            self.duracion_en(lugares[b])
+                .total_cmp(&self.duracion_en(lugares[a]))
+        });
+        indices
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::angulo_del_sol::AnguloDelSol;
+    use crate::flatlanders::crear_intervalo_de_flatlanders;
+    use crate::intervalos::calcular_area;
+    use crate::modelo_de_luz::ModeloDeLuz;
+
+    #[test]
+    fn test_sol_fijo_coincide_con_calcular_area() {
+        let flatlanders = vec![
+            Flatlander::new(50, 150, 30),
+            Flatlander::new(0, 100, 30),
+            Flatlander::new(100, 200, 30),
+        ];
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(30.0),
+        };
+        let area = calcular_area(&mut crear_intervalo_de_flatlanders(&flatlanders, &sol).unwrap());
+        let trayectoria = TrayectoriaSolar::desde_perfil(8.0, 11.0, 6, |_| 30.0).unwrap();
+
+        let horas = HorasDeSombra::new(&flatlanders, &trayectoria);
+        assert!((horas.total() - 3.0 * area).abs() < 1e-6);
+        assert!((horas.duracion_en(120.0) - 3.0).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_arco_diario_sombra_mas_larga_junto_al_flatlander() {
+        let flatlanders = vec![Flatlander::new(100, 10, 45)];
+        let trayectoria = TrayectoriaSolar::arco_diario(6.0, 18.0, 120).unwrap();
+
+        let horas = HorasDeSombra::new(&flatlanders, &trayectoria);
+        let cerca = horas.duracion_en(101.0);
+        let lejos = horas.duracion_en(130.0);
+
+        assert!(cerca > lejos && lejos > 0.0);
+        assert!(cerca < 12.0);
+        assert_eq!(horas.ordenar_lugares(&[130.0, 500.0, 101.0]), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_sin_flatlanders() {
+        let trayectoria = TrayectoriaSolar::arco_diario(6.0, 18.0, 10).unwrap();
+        let horas = HorasDeSombra::new(&[], &trayectoria);
+
+        assert!(horas.tramos().is_empty());
+        assert_eq!(horas.total(), 0.0);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::PRECISION_IGUALDAD_FLOAT;
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::conjunto_de_sombras::ConjuntoDeSombras;
+use crate::flatlanders::Flatlander;
+use crate::intervalos::Intervalo;
+
+/// Cuánto del cuerpo de un flatlander recibe sol directo.
+///
+/// # Variantes
+///
+/// * `Iluminado` - Todo el flatlander recibe sol
+/// * `ParcialmenteSombreado` - Una parte está en sombra; `fraccion_iluminada` es la
+///   fracción de los rayos que le llegarían sin los demás y recibe, entre 0 y 1 (sin
+///   incluirlos). Para un poste es la fracción de su altura que recibe sol
+/// * `Sombreado` - Todo el flatlander está en la sombra de otro
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::iluminacion::{Iluminacion, calcular_iluminacion};
+///
+/// let flatlanders = vec![
+///     Flatlander::new(0, 10, 45),
+///     Flatlander::new(5, 10, 45), // El rayo que pasa por la cabeza del primero llega a 5
+///     Flatlander::new(8, 3, 45),  // El rayo que pasa por la cabeza del segundo llega a 7
+/// ];
+///
+/// let iluminacion = calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(45.0));
+/// assert_eq!(iluminacion[0], Iluminacion::Iluminado);
+/// assert!(matches!(
+///     iluminacion[1],
+///     Iluminacion::ParcialmenteSombreado { fraccion_iluminada } if (fraccion_iluminada - 0.5).abs() < 1e-9
+/// ));
+/// assert_eq!(iluminacion[2], Iluminacion::Sombreado);
+/// ```
+#[derive(Debug, PartialEq)]
+pub enum Iluminacion {
+    Iluminado,
+    ParcialmenteSombreado { fraccion_iluminada: f64 },
+    Sombreado,
+}
+
+impl Iluminacion {
+    /// Clasifica a un flatlander según la parte de sus rayos que queda en sombra, medida
+    /// como altura sobre `x = 0`.
+    ///
+    /// Usa una tolerancia relativa a la altura, para que los casos justos con entradas
+    /// enteras no dependan del redondeo de la tangente.
+    fn desde_altura_sombreada(sombreada: f64, altura: f64) -> Self {
+        let tolerancia = PRECISION_IGUALDAD_FLOAT * altura;
+        if sombreada <= tolerancia {
+            Iluminacion::Iluminado
+        } else if sombreada >= altura - tolerancia {
+            Iluminacion::Sombreado
+        } else {
+            Iluminacion::ParcialmenteSombreado {
+                fraccion_iluminada: 1.0 - sombreada / altura,
+            }
+        }
+    }
+}
+
+/// Calcula para cada flatlander cuánto de su cuerpo recibe sol.
+///
+/// Cada rayo de sol se identifica por la altura `y + x tan(theta)` a la que cruza `x = 0`
+/// (con el sol al oeste), que es la misma en todos sus puntos. Los rayos que tocan el
+/// cuerpo de un flatlander forman un intervalo, que va de la esquina de abajo del lado
+/// del sol a la de arriba del otro lado, o de un vértice a otro de su silueta. Un
+/// flatlander que flota deja pasar por debajo los rayos más bajos.
+///
+/// Alcanza con barrer los flatlanders en orden, desde el lado del sol, juntando en un
+/// `ConjuntoDeSombras` los rayos que tapan: cada uno queda en sombra en la parte de sus
+/// rayos que ya tapó otro. Un cuerpo que se superpone en `x` con otro que está más lejos
+/// del sol se trata como si estuviera entero delante de él, y dos flatlanders en la
+/// misma posición no se tapan entre sí.
+///
+/// # Argumentos
+///
+/// * `flatlanders` - Los flatlanders parados sobre el camino
+/// * `theta` - El ángulo del sol
+///
+/// # Retorna
+///
+/// Un `Vec<Iluminacion>` con la iluminación de cada flatlander, en el orden recibido.
+pub fn calcular_iluminacion(flatlanders: &[Flatlander], theta: &AnguloDelSol) -> Vec<Iluminacion> {
+    let mut resultado: Vec<Iluminacion> =
+        flatlanders.iter().map(|_| Iluminacion::Iluminado).collect();
+
+    // Avance horizontal de los rayos por unidad de altura, negativo con el sol al este.
+    // Con el sol justo encima nadie le hace sombra a nadie
+    let avance = theta.longitud_de_sombra(1.0);
+    if avance == 0.0 {
+        return resultado;
+    }
+
+    // Reflejamos las posiciones si el sol está al este, para barrer siempre desde el sol
+    let (signo, tangente) = (avance.signum(), 1.0 / avance.abs());
+    let contornos: Vec<Vec<(f64, f64)>> = flatlanders
+        .iter()
+        .map(|f| {
+            f.contorno()
+                .into_iter()
+                .map(|(x, y)| (signo * x, y))
+                .collect()
+        })
+        .collect();
+    let posicion = |i: usize| contornos[i].iter().fold(f64::INFINITY, |x, p| x.min(p.0));
+    let rayos = |i: usize| {
+        let alturas = contornos[i].iter().map(|&(x, y)| y + x * tangente);
+        Intervalo {
+            inicio: alturas.clone().fold(f64::INFINITY, f64::min),
+            fin: alturas.fold(f64::NEG_INFINITY, f64::max),
+        }
+    };
+
+    let mut orden: Vec<usize> = (0..flatlanders.len()).collect();
+    orden.sort_by(|&a, &b| posicion(a).total_cmp(&posicion(b)));
+
+    let mut tapados = ConjuntoDeSombras::new();
+    for grupo in orden.chunk_by(|&a, &b| posicion(a) == posicion(b)) {
+        for &i in grupo {
+            let propios = rayos(i);
+            let sombreada = tapados.longitud_cubierta_en(&propios);
+            resultado[i] =
+                Iluminacion::desde_altura_sombreada(sombreada, propios.fin - propios.inicio);
+        }
+        for &i in grupo {
+            tapados.insertar(rayos(i));
+        }
+    }
+
+    resultado
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::silueta::Silueta;
+
+    fn assert_parcial(actual: &Iluminacion, esperada: f64) {
+        match actual {
+            Iluminacion::ParcialmenteSombreado { fraccion_iluminada } => {
+                assert!((fraccion_iluminada - esperada).abs() < PRECISION_IGUALDAD_FLOAT)
+            }
+            otra => panic!("Se esperaba una sombra parcial y se obtuvo {otra:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sin_flatlanders() {
+        assert!(calcular_iluminacion(&[], &AnguloDelSol::Grados(45.0)).is_empty());
+    }
+
+    #[test]
+    fn test_sol_vertical_ilumina_a_todos() {
+        let flatlanders = vec![Flatlander::new(0, 10, 90), Flatlander::new(1, 2, 90)];
+
+        assert_eq!(
+            calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(90.0)),
+            vec![Iluminacion::Iluminado, Iluminacion::Iluminado]
+        );
+    }
+
+    #[test]
+    fn test_sol_al_este_sombrea_hacia_el_oeste() {
+        // Desordenados a propósito: el resultado respeta el orden recibido
+        let flatlanders = vec![
+            Flatlander::new(0, 2, 135),
+            Flatlander::new(10, 10, 135),
+            Flatlander::new(6, 8, 135),
+        ];
+
+        // El rayo de la cabeza del de x=10 llega a 6 con altura 6, y el del de x=6 llega
+        // a 0 justo a la altura 2
+        let iluminacion = calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(135.0));
+        assert_eq!(iluminacion[0], Iluminacion::Sombreado);
+        assert_eq!(iluminacion[1], Iluminacion::Iluminado);
+        assert_parcial(&iluminacion[2], 0.25);
+    }
+
+    #[test]
+    fn test_misma_posicion_no_se_tapan() {
+        let flatlanders = vec![Flatlander::new(4, 10, 45), Flatlander::new(4, 2, 45)];
+
+        assert_eq!(
+            calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(45.0)),
+            vec![Iluminacion::Iluminado, Iluminacion::Iluminado]
+        );
+    }
+
+    #[test]
+    fn test_borde_este_de_un_flatlander_ancho() {
+        // El rayo sale de la cabeza del borde este, en x = 10, y llega a 15 a altura 5
+        let flatlanders = vec![
+            Flatlander::new(0, 10, 45).con_ancho(10),
+            Flatlander::new(15, 4, 45),
+        ];
+
+        let iluminacion = calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(45.0));
+        assert_eq!(iluminacion[1], Iluminacion::Sombreado);
+    }
+
+    #[test]
+    fn test_la_luz_pasa_debajo_de_un_flatlander_que_flota() {
+        // El que flota tapa los rayos entre las alturas 15 y 20 en x = 0, que llegan a
+        // x = 3 entre 12 y 17, por encima del poste de altura 2
+        let flatlanders = vec![
+            Flatlander::new(0, 20, 45).con_elevacion(15).unwrap(),
+            Flatlander::new(3, 2, 45),
+            Flatlander::new(3, 14, 45).con_ancho(1),
+        ];
+
+        let iluminacion = calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(45.0));
+        assert_eq!(iluminacion[1], Iluminacion::Iluminado);
+        // El ancho recibe rayos entre 3 y 18, y de esos están tapados los de 15 a 18
+        assert_parcial(&iluminacion[2], 0.8);
+    }
+
+    #[test]
+    fn test_silueta_tapa_con_sus_vertices() {
+        // Techo inclinado de 6 de ancho que sube hasta 8 en su borde este
+        let techo = Silueta::new(vec![(0.0, 0.0), (6.0, 8.0), (6.0, 0.0)]).unwrap();
+        let theta = AnguloDelSol::Grados(45.0);
+        let flatlanders = vec![
+            Flatlander::con_silueta(0, techo, &theta),
+            Flatlander::new(10, 4, 45),
+        ];
+
+        // El rayo del vértice (6, 8) llega a x = 10 a altura 4
+        let iluminacion = calcular_iluminacion(&flatlanders, &theta);
+        assert_eq!(iluminacion[1], Iluminacion::Sombreado);
+    }
+
+    #[test]
+    fn test_angulo_distinto_al_de_creacion() {
+        // Creados con 45°, pero con el sol a 60° el rayo del primero llega a 5 más bajo
+        let flatlanders = vec![Flatlander::new(0, 10, 45), Flatlander::new(5, 10, 45)];
+        let tangente = 60.0_f64.to_radians().tan();
+
+        let iluminacion = calcular_iluminacion(&flatlanders, &AnguloDelSol::Grados(60.0));
+        assert_parcial(&iluminacion[1], 1.0 - (10.0 - 5.0 * tangente) / 10.0);
+    }
+}
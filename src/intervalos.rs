@@ -1,4 +1,6 @@
 //use crate::PRECISION_IGUALDAD_FLOAT;
+use crate::area_certificada::AreaCertificada;
+use crate::coordenada::Coordenada;
 
 //Implemento estos traits, sin embargo no uso los metodos .clone() ni .copy()
 //Esto lo hago ya que no necesito duplicar intervalos, solo necesito acceder a ellos
@@ -7,15 +9,105 @@
 
 /// Representa un intervalo de números reales con un punto de inicio y fin.
 ///
+/// Por defecto los extremos son `f64`, pero puede usarse cualquier tipo que implemente
+/// `Coordenada`, como enteros o `Racional`, para trabajar con valores exactos.
+///
 /// # Campos
 ///
 /// * `inicio` - El punto de inicio del intervalo
 /// * `fin` - El punto final del intervalo
 ///
 /// Ambos incluidos en el intervalo.
-pub struct Intervalo {
-    pub inicio: f64,
-    pub fin: f64,
+pub struct Intervalo<T = f64> {
+    pub inicio: T,
+    pub fin: T,
+}
+
+/// Fusiona los intervalos que se superponen y retorna los tramos resultantes.
+///
+/// Los intervalos que solo se tocan en un extremo también se fusionan.
+///
+/// # Argumentos
+///
+/// * `intervalos` - El slice mutable de intervalos, que se ordena in place por su inicio
+///
+/// # Retorna
+///
+/// Un `Vec<Intervalo>` con los tramos disjuntos y ordenados por inicio
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, fusionar_intervalos};
+///
+/// let mut intervalos = vec![
+///     Intervalo { inicio: 20.0, fin: 25.0 },
+///     Intervalo { inicio: 0.0, fin: 10.0 },
+///     Intervalo { inicio: 5.0, fin: 15.0 },
+/// ];
+///
+/// let fusionados = fusionar_intervalos(&mut intervalos);
+///
+/// assert_eq!(
+///     fusionados,
+///     vec![
+///         Intervalo { inicio: 0.0, fin: 15.0 },
+///         Intervalo { inicio: 20.0, fin: 25.0 },
+///     ]
+/// );
+/// ```
+pub fn fusionar_intervalos<T: Coordenada>(intervalos: &mut [Intervalo<T>]) -> Vec<Intervalo<T>> {
+    // Ordenar in place
+    intervalos.sort_by(|a, b| a.inicio.comparar(&b.inicio));
+
+    fusionar_ordenados(intervalos)
+}
+
+/// Fusiona intervalos que ya están ordenados por inicio, en una sola pasada.
+///
+/// Sirve para no volver a ordenar cuando el orden se conoce de antemano. Si los
+/// intervalos no están ordenados el resultado no es válido.
+///
+/// # Argumentos
+///
+/// * `intervalos` - Los intervalos, ordenados por inicio
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, fusionar_ordenados};
+///
+/// let intervalos = [
+///     Intervalo { inicio: 0.0, fin: 10.0 },
+///     Intervalo { inicio: 5.0, fin: 8.0 },
+///     Intervalo { inicio: 12.0, fin: 15.0 },
+/// ];
+///
+/// assert_eq!(
+///     fusionar_ordenados(&intervalos),
+///     vec![
+///         Intervalo { inicio: 0.0, fin: 10.0 },
+///         Intervalo { inicio: 12.0, fin: 15.0 },
+///     ]
+/// );
+/// ```
+pub fn fusionar_ordenados<T: Coordenada>(intervalos: &[Intervalo<T>]) -> Vec<Intervalo<T>> {
+    let mut fusionados: Vec<Intervalo<T>> = Vec::new();
+
+    for intervalo in intervalos.iter() {
+        match fusionados.last_mut() {
+            // Si se chocan con el último tramo, los fusionamos
+            Some(ultimo) if intervalo.inicio <= ultimo.fin => {
+                if intervalo.fin > ultimo.fin {
+                    ultimo.fin = intervalo.fin;
+                }
+            }
+            //Sino, empezamos un tramo nuevo
+            _ => fusionados.push(*intervalo),
+        }
+    }
+
+    fusionados
 }
 
 /// Calcula el área total cubierta mediante una suma de intervalos, fusionando los que se superponen.
@@ -26,7 +118,7 @@ pub struct Intervalo {
 ///
 /// # Retorna
 ///
-/// Un `f64` que representa la longitud total cubierta
+/// Un `T` que representa la longitud total cubierta
 ///
 /// # Ejemplo
 ///
@@ -43,38 +135,274 @@ pub struct Intervalo {
 /// assert_eq!(area, 15.0); // Los intervalos se fusionan en [0, 15]
 /// ```
 ///
-pub fn calcular_area(intervalos: &mut [Intervalo]) -> f64 {
-    if intervalos.is_empty() {
-        return 0.0;
-    }
+pub fn calcular_area<T: Coordenada>(intervalos: &mut [Intervalo<T>]) -> T {
+    longitud_total(&fusionar_intervalos(intervalos))
+}
 
-    // Ordenar in place
-    intervalos.sort_by(|a, b| a.inicio.total_cmp(&b.inicio));
+/// Suma las longitudes de una lista de intervalos, sin fusionarlos.
+///
+/// Solo coincide con la longitud cubierta si los intervalos son disjuntos, como los
+/// que retornan `fusionar_intervalos` o `calcular_huecos_iluminados`.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, longitud_total};
+///
+/// let intervalos = [
+///     Intervalo { inicio: 0.0, fin: 5.0 },
+///     Intervalo { inicio: 10.0, fin: 12.0 },
+/// ];
+/// assert_eq!(longitud_total(&intervalos), 7.0);
+/// ```
+pub fn longitud_total<T: Coordenada>(intervalos: &[Intervalo<T>]) -> T {
+    intervalos
+        .iter()
+        .fold(T::cero(), |suma, tramo| suma + (tramo.fin - tramo.inicio))
+}
+
+/// Calcula los tramos iluminados de una ventana del camino.
+///
+/// Recorta los tramos de sombra a la ventana `[inicio, fin]` y retorna su complemento
+/// dentro de ella, es decir, los tramos donde da el sol.
+///
+/// # Argumentos
+///
+/// * `fusionados` - Tramos de sombra disjuntos y ordenados, como los de `fusionar_intervalos`
+/// * `ventana` - El tramo del camino a analizar
+///
+/// # Retorna
+///
+/// Un `Vec<Intervalo>` con los tramos iluminados, disjuntos y ordenados por inicio.
+/// Si la ventana tiene `fin < inicio` el resultado es vacío.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, calcular_huecos_iluminados};
+///
+/// let sombras = [
+///     Intervalo { inicio: 0.0, fin: 10.0 },
+///     Intervalo { inicio: 20.0, fin: 30.0 },
+/// ];
+/// let ventana = Intervalo { inicio: 5.0, fin: 40.0 };
+///
+/// assert_eq!(
+///     calcular_huecos_iluminados(&sombras, &ventana),
+///     vec![
+///         Intervalo { inicio: 10.0, fin: 20.0 },
+///         Intervalo { inicio: 30.0, fin: 40.0 },
+///     ]
+/// );
+/// ```
+pub fn calcular_huecos_iluminados<T: Coordenada>(
+    fusionados: &[Intervalo<T>],
+    ventana: &Intervalo<T>,
+) -> Vec<Intervalo<T>> {
+    let mut huecos = Vec::new();
+    let mut cursor = ventana.inicio;
+
+    // Salteamos con búsqueda binaria los tramos que terminan antes de la ventana
+    let primero = fusionados.partition_point(|tramo| tramo.fin <= ventana.inicio);
+
+    for tramo in fusionados[primero..].iter() {
+        if tramo.inicio >= ventana.fin {
+            break;
+        }
+        if tramo.inicio > cursor {
+            huecos.push(Intervalo {
+                inicio: cursor,
+                fin: tramo.inicio,
+            });
+        }
+        if tramo.fin > cursor {
+            cursor = tramo.fin;
+        }
+    }
 
-    let mut area_total = 0.0;
+    //Agregamos el hueco final, si la última sombra no llega al fin de la ventana
+    if cursor < ventana.fin {
+        huecos.push(Intervalo {
+            inicio: cursor,
+            fin: ventana.fin,
+        });
+    }
 
-    //seteamos el primer intervalo
-    let mut inicio_actual = intervalos[0].inicio;
-    let mut fin_actual = intervalos[0].fin;
+    huecos
+}
+/// Calcula la intersección de dos listas de tramos disjuntos y ordenados.
+///
+/// Recorre ambas listas a la vez, avanzando siempre en la que tiene el tramo que
+/// termina primero.
+///
+/// # Argumentos
+///
+/// * `a` - Tramos disjuntos y ordenados, como los de `fusionar_intervalos`
+/// * `b` - Tramos disjuntos y ordenados, como los de `fusionar_intervalos`
+///
+/// # Retorna
+///
+/// Un `Vec<Intervalo>` con los tramos cubiertos por ambas listas, disjuntos y ordenados.
+/// Los tramos de longitud cero, donde las listas solo se tocan, no se incluyen.
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, intersecar_intervalos};
+///
+/// let a = [Intervalo { inicio: 0.0, fin: 10.0 }, Intervalo { inicio: 20.0, fin: 30.0 }];
+/// let b = [Intervalo { inicio: 5.0, fin: 25.0 }];
+///
+/// assert_eq!(
+///     intersecar_intervalos(&a, &b),
+///     vec![
+///         Intervalo { inicio: 5.0, fin: 10.0 },
+///         Intervalo { inicio: 20.0, fin: 25.0 },
+///     ]
+/// );
+/// ```
+pub fn intersecar_intervalos<T: Coordenada>(
+    a: &[Intervalo<T>],
+    b: &[Intervalo<T>],
+) -> Vec<Intervalo<T>> {
+    let mut interseccion = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        let inicio = if a[i].inicio > b[j].inicio {
+            a[i].inicio
+        } else {
+            b[j].inicio
+        };
+        let fin = if a[i].fin < b[j].fin {
+            a[i].fin
+        } else {
+            b[j].fin
+        };
+        if inicio < fin {
+            interseccion.push(Intervalo { inicio, fin });
+        }
 
-    // Usar iterador saltando el primer elemento (que ya procesamos)
-    for intervalo in intervalos.iter().skip(1) {
-        // Preguntamos si se chocan los intervalos
-        if intervalo.inicio <= fin_actual {
-            //Si se chocan, los fusionamos
-            fin_actual = fin_actual.max(intervalo.fin);
+        // Avanzamos en la lista cuyo tramo termina primero
+        if a[i].fin < b[j].fin {
+            i += 1;
         } else {
-            //Sino, guardamos el area y empezamos uno nuevo
-            area_total += fin_actual - inicio_actual;
-            inicio_actual = intervalo.inicio;
-            fin_actual = intervalo.fin;
+            j += 1;
         }
     }
 
-    //Agregamos el ultimo intervalo
-    area_total += fin_actual - inicio_actual;
+    interseccion
+}
 
-    area_total
+/// Calcula el área total cubierta junto con una cota rigurosa de su error.
+///
+/// Como la longitud de la unión nunca disminuye al agrandar un intervalo, achicar cada
+/// intervalo según su margen de error da una cota inferior y agrandarlo da una cota
+/// superior. Ambas se suman redondeando hacia abajo y hacia arriba respectivamente.
+/// La estimación central usa suma compensada sobre los extremos de los tramos fusionados.
+///
+/// # Argumentos
+///
+/// * `intervalos` - El slice mutable de intervalos, que se ordena in place por su inicio
+/// * `error_relativo` - Cota del error de cada extremo, relativa a la longitud del
+///   intervalo y sin contar el redondeo del propio extremo
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::{Intervalo, calcular_area_certificada};
+///
+/// let mut intervalos = vec![
+///     Intervalo { inicio: 0.0, fin: 10.0 },
+///     Intervalo { inicio: 5.0, fin: 15.0 },
+/// ];
+///
+/// let certificada = calcular_area_certificada(&mut intervalos, 1e-12);
+/// assert_eq!(certificada.area, 15.0);
+/// assert!(certificada.cota_inferior <= 15.0 && 15.0 <= certificada.cota_superior);
+/// ```
+pub fn calcular_area_certificada(
+    intervalos: &mut [Intervalo],
+    error_relativo: f64,
+) -> AreaCertificada {
+    let mut achicados: Vec<Intervalo> = intervalos
+        .iter()
+        .filter_map(|i| achicar(i, margen_de_error(i, error_relativo)))
+        .collect();
+    let mut agrandados: Vec<Intervalo> = intervalos
+        .iter()
+        .map(|i| agrandar(i, margen_de_error(i, error_relativo)))
+        .collect();
+
+    AreaCertificada {
+        area: longitud_compensada(&fusionar_intervalos(intervalos)),
+        cota_inferior: longitud_redondeada(&fusionar_intervalos(&mut achicados), false),
+        cota_superior: longitud_redondeada(&fusionar_intervalos(&mut agrandados), true),
+    }
+}
+
+/// Calcula cuánto puede alejarse cada extremo de un intervalo de su valor exacto.
+///
+/// Suma al error relativo a la longitud un ulp del extremo de mayor magnitud, que cubre
+/// el redondeo con el que se calculó el propio extremo.
+fn margen_de_error(intervalo: &Intervalo, error_relativo: f64) -> f64 {
+    let magnitud = intervalo.inicio.abs().max(intervalo.fin.abs());
+    let ulp = magnitud.next_up() - magnitud;
+    ((intervalo.fin - intervalo.inicio) * error_relativo + ulp).next_up()
+}
+
+/// Achica un intervalo en `margen` de cada lado, redondeando hacia adentro.
+///
+/// Retorna `None` si el intervalo desaparece.
+fn achicar(intervalo: &Intervalo, margen: f64) -> Option<Intervalo> {
+    let inicio = (intervalo.inicio + margen).next_up();
+    let fin = (intervalo.fin - margen).next_down();
+    (inicio <= fin).then_some(Intervalo { inicio, fin })
+}
+
+/// Agranda un intervalo en `margen` de cada lado, redondeando hacia afuera.
+fn agrandar(intervalo: &Intervalo, margen: f64) -> Intervalo {
+    Intervalo {
+        inicio: (intervalo.inicio - margen).next_down(),
+        fin: (intervalo.fin + margen).next_up(),
+    }
+}
+
+/// Suma las longitudes de tramos disjuntos corrigiendo cada resultado parcial un ulp
+/// hacia arriba o hacia abajo, para que la suma sea una cota del valor exacto.
+fn longitud_redondeada(fusionados: &[Intervalo], hacia_arriba: bool) -> f64 {
+    let redondear = |valor: f64| {
+        if hacia_arriba {
+            valor.next_up()
+        } else {
+            valor.next_down()
+        }
+    };
+
+    fusionados.iter().fold(0.0, |suma, tramo| {
+        let longitud = redondear(tramo.fin - tramo.inicio).max(0.0);
+        redondear(suma + longitud).max(0.0)
+    })
+}
+
+/// Suma las longitudes de tramos disjuntos con el algoritmo de Neumaier.
+///
+/// Suma los extremos por separado, así el error de cada resta también queda compensado.
+fn longitud_compensada(fusionados: &[Intervalo]) -> f64 {
+    let (mut suma, mut compensacion) = (0.0_f64, 0.0_f64);
+
+    for valor in fusionados.iter().flat_map(|t| [t.fin, -t.inicio]) {
+        let nueva_suma = suma + valor;
+        // Recuperamos exactamente lo que se perdió al redondear la suma
+        compensacion += if suma.abs() >= valor.abs() {
+            (suma - nueva_suma) + valor
+        } else {
+            (valor - nueva_suma) + suma
+        };
+        suma = nueva_suma;
+    }
+
+    suma + compensacion
 }
 //
 //
@@ -85,6 +413,7 @@ pub fn calcular_area(intervalos: &mut [Intervalo]) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::racional::Racional;
 
     #[test]
     fn test_calcular_area_sin_superposicion() {
@@ -263,6 +592,255 @@ mod tests {
         assert_eq!(area, 10.0);
     }
 
+    #[test]
+    fn test_fusionar_intervalos_desordenados() {
+        let mut intervalos = vec![
+            Intervalo {
+                inicio: 12.0,
+                fin: 18.0,
+            },
+            Intervalo {
+                inicio: 0.0,
+                fin: 5.0,
+            },
+            Intervalo {
+                inicio: 10.0,
+                fin: 15.0,
+            },
+            Intervalo {
+                inicio: 3.0,
+                fin: 8.0,
+            },
+        ];
+
+        let fusionados = fusionar_intervalos(&mut intervalos);
+        assert_eq!(
+            fusionados,
+            vec![
+                Intervalo {
+                    inicio: 0.0,
+                    fin: 8.0,
+                },
+                Intervalo {
+                    inicio: 10.0,
+                    fin: 18.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fusionar_intervalos_vacio() {
+        let mut intervalos: Vec<Intervalo> = vec![];
+        assert!(fusionar_intervalos(&mut intervalos).is_empty());
+    }
+
+    #[test]
+    fn test_huecos_iluminados_ventana_sin_sombras() {
+        let ventana = Intervalo {
+            inicio: 0.0,
+            fin: 10.0,
+        };
+
+        let huecos = calcular_huecos_iluminados(&[], &ventana);
+        assert_eq!(huecos, vec![ventana]);
+    }
+
+    #[test]
+    fn test_huecos_iluminados_ventana_totalmente_sombreada() {
+        let sombras = [Intervalo {
+            inicio: 0.0,
+            fin: 50.0,
+        }];
+        let ventana = Intervalo {
+            inicio: 10.0,
+            fin: 20.0,
+        };
+
+        assert!(calcular_huecos_iluminados(&sombras, &ventana).is_empty());
+    }
+
+    #[test]
+    fn test_huecos_iluminados_recorta_sombras_en_los_bordes() {
+        let sombras = [
+            Intervalo {
+                inicio: -5.0,
+                fin: 2.0,
+            },
+            Intervalo {
+                inicio: 4.0,
+                fin: 6.0,
+            },
+            Intervalo {
+                inicio: 8.0,
+                fin: 15.0,
+            },
+            Intervalo {
+                inicio: 30.0,
+                fin: 40.0,
+            },
+        ];
+        let ventana = Intervalo {
+            inicio: 0.0,
+            fin: 10.0,
+        };
+
+        let huecos = calcular_huecos_iluminados(&sombras, &ventana);
+        assert_eq!(
+            huecos,
+            vec![
+                Intervalo {
+                    inicio: 2.0,
+                    fin: 4.0,
+                },
+                Intervalo {
+                    inicio: 6.0,
+                    fin: 8.0,
+                },
+            ]
+        );
+        assert_eq!(longitud_total(&huecos), 4.0);
+    }
+
+    #[test]
+    fn test_area_certificada_contiene_valor_exacto() {
+        // Con decimales que no son representables exactamente en binario
+        let mut intervalos: Vec<Intervalo> = (0..1000)
+            .map(|i| Intervalo {
+                inicio: i as f64 * 0.3,
+                fin: i as f64 * 0.3 + 0.1,
+            })
+            .collect();
+
+        let certificada = calcular_area_certificada(&mut intervalos, 1e-12);
+        assert!(certificada.cota_inferior <= 100.0 && 100.0 <= certificada.cota_superior);
+        assert!((certificada.area - 100.0).abs() < 1e-9);
+        assert!(certificada.error_maximo() < 1e-9);
+    }
+
+    #[test]
+    fn test_area_certificada_con_error_relativo() {
+        let mut intervalos = vec![Intervalo {
+            inicio: 0.0,
+            fin: 10.0,
+        }];
+
+        let certificada = calcular_area_certificada(&mut intervalos, 0.01);
+        assert_eq!(certificada.area, 10.0);
+        assert!(certificada.cota_inferior <= 9.8 && certificada.cota_inferior > 9.79);
+        assert!(certificada.cota_superior >= 10.2 && certificada.cota_superior < 10.21);
+    }
+
+    #[test]
+    fn test_area_certificada_vacia() {
+        let certificada = calcular_area_certificada(&mut [], 1e-12);
+        assert_eq!(certificada.area, 0.0);
+        assert_eq!(certificada.cota_inferior, 0.0);
+        assert_eq!(certificada.cota_superior, 0.0);
+    }
+
+    #[test]
+    fn test_calcular_area_enteros_sin_signo() {
+        let mut intervalos: Vec<Intervalo<u64>> = vec![
+            Intervalo {
+                inicio: 10,
+                fin: 15,
+            },
+            Intervalo { inicio: 0, fin: 5 },
+            Intervalo { inicio: 3, fin: 8 },
+        ];
+
+        assert_eq!(calcular_area(&mut intervalos), 13);
+    }
+
+    #[test]
+    fn test_fusionar_intervalos_enteros_negativos() {
+        let mut intervalos: Vec<Intervalo<i64>> = vec![
+            Intervalo { inicio: -5, fin: 0 },
+            Intervalo {
+                inicio: -10,
+                fin: -5,
+            },
+            Intervalo { inicio: 2, fin: 4 },
+        ];
+
+        assert_eq!(
+            fusionar_intervalos(&mut intervalos),
+            vec![
+                Intervalo {
+                    inicio: -10,
+                    fin: 0
+                },
+                Intervalo { inicio: 2, fin: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calcular_area_racionales_exacta() {
+        let tercio = |n: i128| Racional::new(n, 3).unwrap();
+        let mut intervalos = vec![
+            Intervalo {
+                inicio: tercio(0),
+                fin: tercio(1),
+            },
+            Intervalo {
+                inicio: tercio(1),
+                fin: tercio(2),
+            },
+            Intervalo {
+                inicio: tercio(4),
+                fin: tercio(5),
+            },
+        ];
+
+        assert_eq!(calcular_area(&mut intervalos), Racional::new(1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_huecos_iluminados_enteros() {
+        let sombras: [Intervalo<i64>; 1] = [Intervalo { inicio: 3, fin: 6 }];
+        let ventana = Intervalo { inicio: 0, fin: 10 };
+
+        let huecos = calcular_huecos_iluminados(&sombras, &ventana);
+        assert_eq!(longitud_total(&huecos), 7);
+    }
+
+    #[test]
+    fn test_intersecar_intervalos() {
+        let a: [Intervalo<i64>; 3] = [
+            Intervalo { inicio: 0, fin: 5 },
+            Intervalo { inicio: 8, fin: 12 },
+            Intervalo {
+                inicio: 15,
+                fin: 20,
+            },
+        ];
+        let b: [Intervalo<i64>; 2] = [
+            Intervalo { inicio: 5, fin: 9 },
+            Intervalo {
+                inicio: 10,
+                fin: 30,
+            },
+        ];
+
+        assert_eq!(
+            intersecar_intervalos(&a, &b),
+            vec![
+                Intervalo { inicio: 8, fin: 9 },
+                Intervalo {
+                    inicio: 10,
+                    fin: 12
+                },
+                Intervalo {
+                    inicio: 15,
+                    fin: 20
+                },
+            ]
+        );
+        assert!(intersecar_intervalos(&a, &[]).is_empty());
+    }
+
     #[test]
     fn test_calcular_area_intervalos_decimales() {
         let mut intervalos = vec![
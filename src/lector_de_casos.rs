@@ -0,0 +1,194 @@
+use std::io::{BufRead, Lines};
+
+/// Separa una entrada con varios casos en el texto de cada caso.
+///
+/// La entrada puede empezar con una línea que tiene solo la cantidad de casos `T`, o
+/// repetir los casos hasta el final. Cada caso es una cabecera, cuyo último valor es la
+/// cantidad `n` de líneas que le siguen, y esas `n` líneas. Las líneas en blanco entre
+/// casos se ignoran.
+///
+/// Cada caso se entrega sin validar, para que un caso inválido no impida leer los
+/// siguientes. Si la cabecera de un caso no tiene una cantidad de líneas legible, no se
+/// puede saber dónde empieza el siguiente: se entrega ese caso y la lectura termina.
+///
+/// # Campos
+///
+/// * `lineas` - Las líneas de la entrada que faltan leer
+/// * `cabecera` - La cabecera del primer caso, si se leyó al buscar la cantidad de casos
+/// * `restantes` - La cantidad de casos que faltan, si la entrada empezó con ella
+/// * `terminado` - Si ya no se pueden leer más casos
+///
+/// # Ejemplo
+///
+/// ```
+/// use std::io::BufReader;
+/// use el_rompecabezas_de_las_sombreas::lector_de_casos::LectorDeCasos;
+///
+/// let input = "2\n45 1\n0 10\n\n30 1\n0 10\n";
+/// let casos: Vec<_> = LectorDeCasos::new(BufReader::new(input.as_bytes()))
+///     .unwrap()
+///     .collect();
+///
+/// assert_eq!(casos, vec![Ok("45 1\n0 10\n".to_string()), Ok("30 1\n0 10\n".to_string())]);
+/// ```
+#[derive(Debug)]
+pub struct LectorDeCasos<R: BufRead> {
+    lineas: Lines<R>,
+    cabecera: Option<String>,
+    restantes: Option<usize>,
+    terminado: bool,
+}
+
+impl<R: BufRead> LectorDeCasos<R> {
+    /// Prepara la lectura, leyendo la cantidad de casos si la entrada empieza con ella.
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::IO` - Si falla la lectura de la primera línea
+    /// * `ErrorTipo::NumeroInvalido` - Si la primera línea tiene un solo valor y no es
+    ///   una cantidad de casos
+    pub fn new(reader: R) -> Result<Self, crate::ErrorTipo> {
+        let mut lector = Self {
+            lineas: reader.lines(),
+            cabecera: None,
+            restantes: None,
+            terminado: false,
+        };
+
+        let Some(primera) = lector.siguiente_linea_con_datos()? else {
+            return Ok(lector);
+        };
+        match primera.split_whitespace().count() {
+            1 => {
+                let cantidad = primera.trim().parse::<usize>();
+                lector.restantes = Some(cantidad.map_err(|_| crate::ErrorTipo::NumeroInvalido)?);
+            }
+            _ => lector.cabecera = Some(primera),
+        }
+        Ok(lector)
+    }
+
+    /// Retorna la próxima línea que no está en blanco, o `None` al final de la entrada.
+    fn siguiente_linea_con_datos(&mut self) -> Result<Option<String>, crate::ErrorTipo> {
+        for linea in self.lineas.by_ref() {
+            let linea = linea.map_err(|_| crate::ErrorTipo::IO)?;
+            if !linea.trim().is_empty() {
+                return Ok(Some(linea));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Lee un caso completo a partir de su cabecera.
+    ///
+    /// Si la entrada termina antes de las `n` líneas, el caso se entrega incompleto y
+    /// falla al procesarlo.
+    fn leer_caso(&mut self, cabecera: String) -> Result<String, crate::ErrorTipo> {
+        let cantidad = cabecera
+            .split_whitespace()
+            .last()
+            .and_then(|token| token.parse::<usize>().ok());
+        let Some(cantidad) = cantidad else {
+            self.terminado = true;
+            return Ok(cabecera + "\n");
+        };
+
+        let mut texto = cabecera + "\n";
+        for linea in self.lineas.by_ref().take(cantidad) {
+            texto.push_str(&linea.map_err(|_| crate::ErrorTipo::IO)?);
+            texto.push('\n');
+        }
+        Ok(texto)
+    }
+}
+
+impl<R: BufRead> Iterator for LectorDeCasos<R> {
+    type Item = Result<String, crate::ErrorTipo>;
+
+    /// Retorna el texto del próximo caso.
+    ///
+    /// Si la entrada anunció más casos de los que tiene, cada caso que falta es un
+    /// `ErrorTipo::LineaFaltante`. Un error de lectura termina la lectura.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.terminado || self.restantes == Some(0) {
+            return None;
+        }
+        if let Some(restantes) = self.restantes.as_mut() {
+            *restantes -= 1;
+        }
+
+        let cabecera = match self.cabecera.take() {
+            Some(cabecera) => Ok(Some(cabecera)),
+            None => self.siguiente_linea_con_datos(),
+        };
+        let caso = match cabecera {
+            Ok(Some(cabecera)) => self.leer_caso(cabecera),
+            Ok(None) if self.restantes.is_some() => Err(crate::ErrorTipo::LineaFaltante),
+            Ok(None) => return None,
+            Err(error) => Err(error),
+        };
+        if caso == Err(crate::ErrorTipo::IO) {
+            self.terminado = true;
+        }
+        Some(caso)
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    fn casos(input: &str) -> Vec<Result<String, crate::ErrorTipo>> {
+        LectorDeCasos::new(BufReader::new(input.as_bytes()))
+            .unwrap()
+            .collect()
+    }
+
+    #[test]
+    fn test_casos_hasta_el_final() {
+        let leidos = casos("45 2\n0 10\n5 15\n30 1\n0 10\n\n");
+        assert_eq!(
+            leidos,
+            vec![
+                Ok("45 2\n0 10\n5 15\n".to_string()),
+                Ok("30 1\n0 10\n".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cantidad_de_casos_menor_a_la_entrada() {
+        let leidos = casos("1\n45 1\n0 10\n30 1\n0 10\n");
+        assert_eq!(leidos, vec![Ok("45 1\n0 10\n".to_string())]);
+    }
+
+    #[test]
+    fn test_casos_anunciados_que_faltan() {
+        let leidos = casos("3\n45 1\n0 10\n");
+        assert_eq!(leidos.len(), 3);
+        assert_eq!(leidos[1], Err(crate::ErrorTipo::LineaFaltante));
+        assert_eq!(leidos[2], Err(crate::ErrorTipo::LineaFaltante));
+    }
+
+    #[test]
+    fn test_cabecera_ilegible_termina_la_lectura() {
+        let leidos = casos("45 x\n0 10\n30 1\n0 10\n");
+        assert_eq!(leidos, vec![Ok("45 x\n".to_string())]);
+    }
+
+    #[test]
+    fn test_entrada_vacia_y_cantidad_invalida() {
+        assert!(casos("").is_empty());
+        assert!(casos("0\n45 1\n0 10\n").is_empty());
+
+        let lector = LectorDeCasos::new(BufReader::new("dos\n".as_bytes()));
+        assert_eq!(lector.err(), Some(crate::ErrorTipo::NumeroInvalido));
+    }
+}
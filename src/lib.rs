@@ -15,13 +15,60 @@
 
 pub const PRECISION_IGUALDAD_FLOAT: f64 = 1e-8; // Precisión para comparar floats
 
+pub mod angulo_del_sol;
+pub mod arbol_de_intervalos;
+pub mod arbol_de_sombras;
+pub mod area_certificada;
+pub mod area_con_penumbra;
+pub mod barrido_de_angulos;
+pub mod busqueda_de_angulo;
+pub mod caja;
+pub mod conjunto_de_sombras;
+pub mod coordenada;
+pub mod escena;
+pub mod espacio;
 pub mod flatlanders;
+pub mod histograma_de_cobertura;
+pub mod horas_de_sombra;
+pub mod iluminacion;
 pub mod intervalos;
+pub mod lector_de_casos;
+pub mod limites;
+pub mod modelo_de_luz;
+pub mod notacion_angular;
+pub mod opciones;
+pub mod optimizador_de_sombra;
 pub mod parametros;
+pub mod parametros_espaciales;
+pub mod perfil_de_oscuridad;
+pub mod poligono;
+pub mod racional;
+pub mod resultado;
+pub mod silueta;
+pub mod sombra;
+pub mod sombra_en_terreno;
+pub mod terreno;
+pub mod trayectoria_solar;
 
-use flatlanders::{crear_intervalo_de_flatlanders, leer_flatlanders_desde_stdin};
-use intervalos::calcular_area;
+use area_con_penumbra::AreaConPenumbra;
+use barrido_de_angulos::BarridoDeAngulos;
+use busqueda_de_angulo::{angulo_para_longitud, rango_de_busqueda};
+use caja::leer_cajas_desde_stdin_con_limites;
+use conjunto_de_sombras::ConjuntoDeSombras;
+use escena::{CoberturaPorFuentes, calcular_cobertura};
+use espacio::calcular_area_de_union;
+use flatlanders::{
+    Flatlander, crear_intervalo_de_flatlanders, leer_flatlanders_desde_stdin_con_limites,
+};
+use intervalos::{Intervalo, calcular_area_certificada, fusionar_intervalos, longitud_total};
+use lector_de_casos::LectorDeCasos;
+use limites::Limites;
+use modelo_de_luz::ModeloDeLuz;
+use opciones::Opciones;
 use parametros::Parametros;
+use parametros_espaciales::ParametrosEspaciales;
+use racional::Racional;
+use resultado::Resultado;
 use std::io::BufRead;
 
 #[derive(Debug, PartialEq)]
@@ -36,12 +83,14 @@ use std::io::BufRead;
 /// * `ValorFaltante` - Linea x con menos valores de los necesarios
 /// * `NumeroInvalido` -  Error al parsear un número
 /// * `LineaFaltante` - Menos lineas de las necesarias
+/// * `ArgumentoInvalido` - Argumento de línea de comandos no reconocido
 pub enum ErrorTipo {
     IO,
     FueraDeRango,
     ValorFaltante,
     NumeroInvalido,
     LineaFaltante,
+    ArgumentoInvalido,
 }
 
 impl ErrorTipo {
@@ -54,6 +103,7 @@ impl ErrorTipo {
     /// * `ValorFaltante` → "Error: \"Valor faltante\""
     /// * `NumeroInvalido` → "Error: \"Numero invalido\""
     /// * `LineaFaltante` → "Error: \"Linea faltante\""
+    /// * `ArgumentoInvalido` → "Error: \"Argumento invalido\""
     ///
     /// # Ejemplo
     ///
@@ -64,14 +114,27 @@ impl ErrorTipo {
     /// error.imprimir_error(); // Imprime: Error: "Numero invalido"
     /// ```
     pub fn imprimir_error(&self) {
-        let mensaje = match self {
-            ErrorTipo::IO => "Error: \"IO\"\n",
-            ErrorTipo::FueraDeRango => "Error: \"Fuera de rango\"\n",
-            ErrorTipo::ValorFaltante => "Error: \"Valor faltante\"\n",
-            ErrorTipo::NumeroInvalido => "Error: \"Numero invalido\"\n",
-            ErrorTipo::LineaFaltante => "Error: \"Linea faltante\"\n",
-        };
-        eprintln!("{}", mensaje);
+        eprintln!("{}\n", self.mensaje());
+    }
+
+    /// Retorna el mensaje de error de `imprimir_error`, sin el salto de línea final.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::ErrorTipo;
+    ///
+    /// assert_eq!(ErrorTipo::LineaFaltante.mensaje(), "Error: \"Linea faltante\"");
+    /// ```
+    pub fn mensaje(&self) -> &'static str {
+        match self {
+            ErrorTipo::IO => "Error: \"IO\"",
+            ErrorTipo::FueraDeRango => "Error: \"Fuera de rango\"",
+            ErrorTipo::ValorFaltante => "Error: \"Valor faltante\"",
+            ErrorTipo::NumeroInvalido => "Error: \"Numero invalido\"",
+            ErrorTipo::LineaFaltante => "Error: \"Linea faltante\"",
+            ErrorTipo::ArgumentoInvalido => "Error: \"Argumento invalido\"",
+        }
     }
 }
 
@@ -80,7 +143,8 @@ impl ErrorTipo {
 /// # Algoritmo:
 /// 1. Lee y valida los parámetros de entrada
 /// 2. Procesa cada flatlander y calcula sus sombras en intervalos
-/// 3. Suma el area de los intervalos (superpuestos o no) y lo retorna
+/// 3. Inserta los intervalos en un `ConjuntoDeSombras` y retorna la longitud de sus
+///    tramos fusionados
 ///
 /// # Argumentos
 ///
@@ -120,17 +184,247 @@ impl ErrorTipo {
 /// }
 /// ```
 pub fn procesar_entrada<R: BufRead>(reader: R) -> Result<f64, ErrorTipo> {
+    procesar_entrada_con_opciones(reader, &Opciones::default()).map(|resultado| resultado.area)
+}
+
+/// Procesa la entrada completa teniendo en cuenta las opciones de ejecución.
+///
+/// Funciona igual que `procesar_entrada`, pero retorna un `Resultado` que además del
+/// área puede incluir los tramos de sombra fusionados y una cota rigurosa del error.
+/// Con la opción `espacial` la entrada describe cajas en tres dimensiones y el área es
+/// la de la unión de sus sombras sobre el suelo.
+///
+/// # Argumentos
+///
+/// * `reader` - Un lector que implementa `BufRead` con los datos de entrada
+/// * `opciones` - Las opciones que indican qué información incluir en el resultado
+///
+/// # Retorna
+///
+/// * `Ok(Resultado)` - El área total cubierta y la información adicional pedida
+/// * `Err(ErrorTipo)` - Si hay errores en la entrada
+///
+/// # Ejemplo
+///
+/// ```
+/// use std::io::BufReader;
+/// use el_rompecabezas_de_las_sombreas::opciones::Opciones;
+/// use el_rompecabezas_de_las_sombreas::procesar_entrada_con_opciones;
+///
+/// let input = "45 2\n0 10\n20 10\n";
+/// let reader = BufReader::new(input.as_bytes());
+/// let opciones = Opciones { mostrar_intervalos: true, ..Opciones::default() };
+///
+/// let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+/// assert_eq!(resultado.intervalos.map(|tramos| tramos.len()), Some(2));
+/// ```
+pub fn procesar_entrada_con_opciones<R: BufRead>(
+    reader: R,
+    opciones: &Opciones,
+) -> Result<Resultado, ErrorTipo> {
     let mut lineas = reader.lines();
+    if opciones.espacial {
+        return procesar_lineas_espaciales(&mut lineas, opciones);
+    }
 
     // Leer y validar parámetros (retorna error si falla)
-    let params = Parametros::leer_desde_stdin(&mut lineas)?;
+    let params = Parametros::leer_desde_stdin_con_notacion(
+        &mut lineas,
+        &opciones.limites,
+        opciones.notacion,
+    )?;
 
     //Leer y validar flatlanders (retorna error si falla)
-    let flatlanders = leer_flatlanders_desde_stdin(&mut lineas, &params)?;
+    let flatlanders =
+        leer_flatlanders_desde_stdin_con_limites(&mut lineas, &params, &opciones.limites)?;
+
+    calcular_resultado(&flatlanders, &params, opciones)
+}
+
+/// Procesa una entrada con varios casos, cada uno con el formato de
+/// `procesar_entrada_con_opciones`.
+///
+/// La entrada puede empezar con la cantidad de casos en una línea, o repetir los casos
+/// hasta el final; ver `lector_de_casos::LectorDeCasos`. Cada caso se procesa por
+/// separado, así que un caso inválido no impide calcular los demás.
+///
+/// # Argumentos
+///
+/// * `reader` - Un lector que implementa `BufRead` con los datos de entrada
+/// * `opciones` - Las opciones que se aplican a todos los casos
+///
+/// # Retorna
+///
+/// * `Ok(Vec<Result<Resultado, ErrorTipo>>)` - El resultado o el error de cada caso, en orden
+/// * `Err(ErrorTipo)` - Si la cantidad de casos no es válida o no se puede leer
+///
+/// # Ejemplo
+///
+/// ```
+/// use std::io::BufReader;
+/// use el_rompecabezas_de_las_sombreas::opciones::Opciones;
+/// use el_rompecabezas_de_las_sombreas::{ErrorTipo, procesar_casos_con_opciones};
+///
+/// let input = "45 1\n0 10\n45 1\n0 x\n45 1\n0 20\n";
+/// let reader = BufReader::new(input.as_bytes());
+///
+/// let casos = procesar_casos_con_opciones(reader, &Opciones::default()).unwrap();
+/// assert_eq!(casos.len(), 3);
+/// assert_eq!(casos[1], Err(ErrorTipo::NumeroInvalido));
+/// assert!(casos[2].as_ref().is_ok_and(|resultado| (resultado.area - 20.0).abs() < 1e-9));
+/// ```
+pub fn procesar_casos_con_opciones<R: BufRead>(
+    reader: R,
+    opciones: &Opciones,
+) -> Result<Vec<Result<Resultado, ErrorTipo>>, ErrorTipo> {
+    Ok(LectorDeCasos::new(reader)?
+        .map(|caso| {
+            caso.and_then(|texto| procesar_entrada_con_opciones(texto.as_bytes(), opciones))
+        })
+        .collect())
+}
+
+/// Procesa una entrada en tres dimensiones: lee las cajas y calcula el área de la unión
+/// de sus sombras sobre el suelo.
+///
+/// # Formato de entrada esperado
+///
+/// ```text
+/// azimut elevacion n
+/// x1 y1 ancho1 largo1 h1
+/// ...
+/// xn yn anchon largon hn
+/// ```
+fn procesar_lineas_espaciales<R: BufRead>(
+    lineas: &mut std::io::Lines<R>,
+    opciones: &Opciones,
+) -> Result<Resultado, ErrorTipo> {
+    let params = ParametrosEspaciales::leer_desde_stdin_con_limites(lineas, &opciones.limites)?;
+    let cajas = leer_cajas_desde_stdin_con_limites(lineas, &params, &opciones.limites)?;
+
+    let sombras: Vec<_> = cajas
+        .iter()
+        .map(|caja| caja.sombra(params.azimut, params.elevacion))
+        .collect();
+
+    Ok(Resultado {
+        area: calcular_area_de_union(&sombras),
+        area_exacta: None,
+        intervalos: None,
+        certificado: None,
+        penumbra: None,
+        angulo: None,
+        barrido: None,
+        fuentes: None,
+    })
+}
+
+/// Calcula el resultado para una escena ya leída y validada.
+///
+/// En modo certificado el área informada es la estimación con suma compensada.
+fn calcular_resultado(
+    flatlanders: &[Flatlander],
+    params: &Parametros,
+    opciones: &Opciones,
+) -> Result<Resultado, ErrorTipo> {
+    //Creo intervalos de cada flatlander con el sol de la entrada y los voy agregando al
+    //conjunto de sombras
+    let sol = ModeloDeLuz::Sol {
+        theta: params.theta,
+    };
+    // Solo un sol fuera de (0, 180) proyecta sombras sin fin
+    let mut intervalos =
+        crear_intervalo_de_flatlanders(flatlanders, &sol).map_err(|_| ErrorTipo::FueraDeRango)?;
+    let mut sombras = ConjuntoDeSombras::new();
+    for intervalo in intervalos.iter() {
+        sombras.insertar(*intervalo);
+    }
+    // El área se suma sobre los tramos en orden, como al ordenar y fusionar, para no
+    // depender del redondeo de la longitud que el conjunto lleva al día
+    let tramos = sombras.into_intervalos();
+
+    let certificado = opciones
+        .certificado
+        .then(|| calcular_area_certificada(&mut intervalos, params.theta.error_relativo()));
+    let area = certificado
+        .as_ref()
+        .map_or(longitud_total(&tramos), |c| c.area);
 
-    //Creo intervalos de cada flatlander, con los cuales luego calcular el área total
-    let mut intervalos = crear_intervalo_de_flatlanders(&flatlanders);
-    let area = calcular_area(&mut intervalos);
+    let rango = rango_de_busqueda(&opciones.limites);
+    let angulo = opciones
+        .objetivo
+        .map(|objetivo| angulo_para_longitud(flatlanders, objetivo, &rango))
+        .transpose()?;
+    let barrido = opciones
+        .barrido
+        .map(|paso| calcular_barrido(flatlanders, paso, &opciones.limites))
+        .transpose()?;
+    let fuentes = (!opciones.soles.is_empty())
+        .then(|| calcular_cobertura_por_fuentes(flatlanders, params, opciones))
+        .transpose()?;
+
+    Ok(Resultado {
+        area,
+        area_exacta: calcular_area_exacta(flatlanders, params),
+        intervalos: opciones.mostrar_intervalos.then_some(tramos),
+        certificado,
+        penumbra: opciones
+            .radio_solar
+            .map(|radio| AreaConPenumbra::new(flatlanders, &params.theta, radio)),
+        angulo,
+        barrido,
+        fuentes,
+    })
+}
+
+/// Ilumina la escena con el sol de la entrada y los soles de las opciones, y calcula la
+/// cobertura por fuentes.
+///
+/// # Errores
+///
+/// * `ErrorTipo::FueraDeRango` - Si algún sol de las opciones está fuera de los límites
+fn calcular_cobertura_por_fuentes(
+    flatlanders: &[Flatlander],
+    params: &Parametros,
+    opciones: &Opciones,
+) -> Result<CoberturaPorFuentes, ErrorTipo> {
+    if !opciones
+        .soles
+        .iter()
+        .all(|theta| theta.esta_en(&opciones.limites.theta))
+    {
+        return Err(ErrorTipo::FueraDeRango);
+    }
+
+    let luces: Vec<ModeloDeLuz> = std::iter::once(params.theta)
+        .chain(opciones.soles.iter().copied())
+        .map(|theta| ModeloDeLuz::Sol { theta })
+        .collect();
+    Ok(calcular_cobertura(flatlanders, &luces))
+}
+
+/// Calcula el área exacta si el sol está dado como pendiente, o `None` si no lo está o
+/// si algún valor exacto no entra en un `Racional`.
+fn calcular_area_exacta(flatlanders: &[Flatlander], params: &Parametros) -> Option<Racional> {
+    let mut sombras: Vec<Intervalo<Racional>> = flatlanders
+        .iter()
+        .map(|f| f.sombra_exacta(&params.theta))
+        .collect::<Option<_>>()?;
+
+    // Fusionar solo compara extremos, que nunca desborda; las sumas se controlan una a una
+    fusionar_intervalos(&mut sombras)
+        .iter()
+        .try_fold(Racional::entero(0), |area, tramo| {
+            area.checked_add(tramo.fin.checked_sub(tramo.inicio)?)
+        })
+}
 
-    Ok(area)
+/// Calcula la tabla de longitudes en sombra para todos los ángulos de los límites.
+fn calcular_barrido(
+    flatlanders: &[Flatlander],
+    paso: f64,
+    limites: &Limites,
+) -> Result<Vec<(f64, f64)>, ErrorTipo> {
+    let rango = *limites.theta.start() as f64..=*limites.theta.end() as f64;
+    BarridoDeAngulos::new(flatlanders).barrer(&rango, paso)
 }
@@ -0,0 +1,58 @@
+use std::ops::RangeInclusive;
+
+/// Rangos válidos para cada valor de la entrada.
+///
+/// # Campos
+///
+/// * `theta` - Rango válido para el ángulo theta del sol, en grados
+/// * `n` - Rango válido para el número de flatlanders
+/// * `x` - Rango válido para la posición X de cada flatlander
+/// * `h` - Rango válido para la altura H de cada flatlander
+/// * `w` - Rango válido para el ancho W opcional de cada flatlander
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::limites::Limites;
+///
+/// assert!(!Limites::concurso().theta.contains(&120));
+/// assert!(Limites::cielo_completo().theta.contains(&120));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Limites {
+    pub theta: RangeInclusive<u32>,
+    pub n: RangeInclusive<u32>,
+    pub x: RangeInclusive<u32>,
+    pub h: RangeInclusive<u32>,
+    pub w: RangeInclusive<u32>,
+}
+
+impl Limites {
+    /// Retorna los límites del enunciado original, con el sol entre 10° y 80°.
+    pub fn concurso() -> Self {
+        Self {
+            theta: 10..=80,
+            n: 1..=100_000,
+            x: 0..=300_000,
+            h: 1..=1000,
+            w: 0..=300_000,
+        }
+    }
+
+    /// Retorna los límites del enunciado, pero con el sol en cualquier ángulo entre
+    /// 0° y 180° (sin incluirlos). Pasados los 90° el sol está al este y las sombras
+    /// caen hacia el oeste.
+    pub fn cielo_completo() -> Self {
+        Self {
+            theta: 1..=179,
+            ..Self::concurso()
+        }
+    }
+}
+
+impl Default for Limites {
+    /// Por defecto se usan los límites del enunciado.
+    fn default() -> Self {
+        Self::concurso()
+    }
+}
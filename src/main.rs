@@ -1,4 +1,9 @@
-use el_rompecabezas_de_las_sombreas::procesar_entrada;
+use el_rompecabezas_de_las_sombreas::area_certificada::TOLERANCIA_DEL_RESULTADO;
+use el_rompecabezas_de_las_sombreas::opciones::Opciones;
+use el_rompecabezas_de_las_sombreas::resultado::Resultado;
+use el_rompecabezas_de_las_sombreas::{
+    ErrorTipo, procesar_casos_con_opciones, procesar_entrada_con_opciones,
+};
 use std::io;
 const CANTIDAD_DE_DECIMALES: usize = 13;
 
@@ -6,11 +11,54 @@ fn main() {
     let stdin = io::stdin();
     let reader = stdin.lock();
 
-    match procesar_entrada(reader) {
-        Ok(area) => print!("{:.prec$}", area, prec = CANTIDAD_DE_DECIMALES),
-        Err(error) => {
-            error.imprimir_error();
-            std::process::exit(1);
+    let opciones = match Opciones::desde_argumentos(std::env::args().skip(1)) {
+        Ok(opciones) => opciones,
+        Err(error) => salir_con_error(error),
+    };
+
+    if opciones.casos {
+        match procesar_casos_con_opciones(reader, &opciones) {
+            Ok(casos) => imprimir_casos(&casos),
+            Err(error) => salir_con_error(error),
         }
+        return;
+    }
+
+    match procesar_entrada_con_opciones(reader, &opciones) {
+        Ok(resultado) => imprimir_resultado(&resultado),
+        Err(error) => salir_con_error(error),
+    }
+}
+
+/// Imprime el resultado por STDOUT.
+fn imprimir_resultado(resultado: &Resultado) {
+    print!("{}", resultado.formatear(CANTIDAD_DE_DECIMALES));
+
+    // En modo certificado avisamos por STDERR si no se puede garantizar la precisión
+    if let Some(certificado) = &resultado.certificado
+        && !certificado.es_confiable(TOLERANCIA_DEL_RESULTADO)
+    {
+        eprintln!("Advertencia: \"Precision no garantizada\"");
     }
 }
+
+/// Imprime el resultado de cada caso, o su error en su lugar, y termina con código 1 si
+/// algún caso falló.
+fn imprimir_casos(casos: &[Result<Resultado, ErrorTipo>]) {
+    for caso in casos {
+        match caso {
+            Ok(resultado) => imprimir_resultado(resultado),
+            Err(error) => print!("{}", error.mensaje()),
+        }
+        println!();
+    }
+
+    if casos.iter().any(Result::is_err) {
+        std::process::exit(1);
+    }
+}
+
+fn salir_con_error(error: ErrorTipo) -> ! {
+    error.imprimir_error();
+    std::process::exit(1);
+}
@@ -0,0 +1,148 @@
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::sombra::Sombra;
+
+/// Modelo de la fuente de luz que ilumina la escena.
+///
+/// # Variantes
+///
+/// * `Sol` - Luz infinitamente lejana, con ángulo `theta` medido desde el horizonte oeste
+/// * `Lampara` - Luz puntual ubicada en la posición `x` a una altura `y` sobre el camino
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::modelo_de_luz::ModeloDeLuz;
+/// use el_rompecabezas_de_las_sombreas::sombra::Sombra;
+///
+/// let farol = ModeloDeLuz::Lampara { x: 0.0, y: 10.0 };
+///
+/// // Por triángulos semejantes, la sombra de un flatlander de altura 5 a 10 del farol
+/// // llega hasta 10 * 10 / (10 - 5) = 20
+/// assert_eq!(farol.proyectar(10.0, 5.0), Sombra::Acotada(Intervalo { inicio: 10.0, fin: 20.0 }));
+/// assert_eq!(farol.proyectar(-10.0, 12.0), Sombra::HaciaElOeste { fin: -10.0 });
+/// ```
+#[derive(Debug, PartialEq)]
+pub enum ModeloDeLuz {
+    Sol { theta: AnguloDelSol },
+    Lampara { x: f64, y: f64 },
+}
+
+impl ModeloDeLuz {
+    /// Proyecta sobre el camino la sombra de un poste ubicado en `x` con la altura dada.
+    ///
+    /// Con el sol la sombra mide `altura / tan(theta)`, hacia el este si `theta < 90` y
+    /// hacia el oeste si `theta > 90` (ver `AnguloDelSol::longitud_de_sombra`). Con una lámpara la
+    /// sombra se aleja de ella hasta donde el rayo que pasa por la punta del poste toca
+    /// el suelo, y no tiene fin si el poste es tan alto como la lámpara o más. Un poste
+    /// justo debajo de la lámpara no proyecta sombra.
+    ///
+    /// # Argumentos
+    ///
+    /// * `x` - La posición del poste en el eje X
+    /// * `altura` - La altura del poste
+    pub fn proyectar(&self, x: f64, altura: f64) -> Sombra {
+        let punta = self.punta(x, altura);
+        Sombra::desde_extremos(x.min(punta), x.max(punta))
+    }
+
+    /// Retorna dónde toca el suelo el rayo de luz que pasa por el punto `(x, altura)`.
+    ///
+    /// Si el rayo nunca llega al suelo (con una lámpara a la misma altura que el punto o
+    /// más abajo) retorna un infinito, con el signo del lado hacia el que se aleja.
+    ///
+    /// # Argumentos
+    ///
+    /// * `x` - La posición del punto en el eje X
+    /// * `altura` - La altura del punto
+    pub fn punta(&self, x: f64, altura: f64) -> f64 {
+        match *self {
+            ModeloDeLuz::Sol { theta } => x + theta.longitud_de_sombra(altura),
+            ModeloDeLuz::Lampara { x: lx, y: ly } => punta_desde_lampara(x, altura, lx, ly),
+        }
+    }
+}
+
+/// Calcula dónde toca el suelo el rayo de una lámpara en `(lx, ly)` que pasa por `(x, altura)`.
+fn punta_desde_lampara(x: f64, altura: f64, lx: f64, ly: f64) -> f64 {
+    if x == lx {
+        return x;
+    }
+
+    if altura >= ly {
+        return if x > lx {
+            f64::INFINITY
+        } else {
+            f64::NEG_INFINITY
+        };
+    }
+
+    // Triángulos semejantes: el rayo baja ly en la distancia (x_sombra - lx)
+    // y baja (ly - altura) en la distancia (x - lx)
+    lx + (x - lx) * ly / (ly - altura)
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::flatlanders::Flatlander;
+    use crate::intervalos::Intervalo;
+
+    #[test]
+    fn test_sol_coincide_con_flatlander() {
+        let flatlander = Flatlander::new(10, 20, 30);
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(30.0),
+        };
+
+        assert_eq!(
+            sol.proyectar(10.0, 20.0),
+            Sombra::Acotada(flatlander.sombra())
+        );
+    }
+
+    #[test]
+    fn test_lampara_sombra_hacia_el_oeste() {
+        let farol = ModeloDeLuz::Lampara { x: 100.0, y: 8.0 };
+        let sombra = farol.proyectar(96.0, 6.0).como_intervalo();
+
+        // La punta cae en 100 - 4 * 8 / 2 = 84
+        assert!((sombra.inicio - 84.0).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((sombra.fin - 96.0).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_lampara_sombras_no_acotadas() {
+        let farol = ModeloDeLuz::Lampara { x: 0.0, y: 5.0 };
+
+        assert_eq!(
+            farol.proyectar(3.0, 5.0),
+            Sombra::HaciaElEste { inicio: 3.0 }
+        );
+        assert_eq!(
+            farol.proyectar(-3.0, 7.0),
+            Sombra::HaciaElOeste { fin: -3.0 }
+        );
+    }
+
+    #[test]
+    fn test_lampara_justo_encima() {
+        let farol = ModeloDeLuz::Lampara { x: 4.0, y: 5.0 };
+        let sombra = farol.proyectar(4.0, 9.0);
+
+        assert_eq!(
+            sombra,
+            Sombra::Acotada(Intervalo {
+                inicio: 4.0,
+                fin: 4.0
+            })
+        );
+    }
+}
@@ -0,0 +1,69 @@
+/// Notación con la que se escribe el ángulo del sol en la entrada.
+///
+/// # Variantes
+///
+/// * `Automatica` - Se deduce del texto: `a/b` es una pendiente, con `°` o `:` son
+///   grados, minutos y segundos, con el sufijo `rad` son radianes, y si no son grados
+///   decimales
+/// * `Grados` - Grados decimales, como `45` o `30.5`
+/// * `GradosMinutosSegundos` - Grados, minutos y segundos, como `30°15'30"` o `30:15:30`
+/// * `Radianes` - Radianes, con o sin el sufijo `rad`, como `0.5236`
+/// * `Pendiente` - La pendiente exacta del rayo de sol como `subida/avance`, como `3/4`
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::notacion_angular::NotacionAngular;
+///
+/// assert_eq!(NotacionAngular::desde_nombre("gms"), Some(NotacionAngular::GradosMinutosSegundos));
+/// assert_eq!(NotacionAngular::default(), NotacionAngular::Automatica);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NotacionAngular {
+    #[default]
+    Automatica,
+    Grados,
+    GradosMinutosSegundos,
+    Radianes,
+    Pendiente,
+}
+
+impl NotacionAngular {
+    /// Retorna la notación con el nombre dado, como se escribe en `--notacion=<nombre>`.
+    ///
+    /// Los nombres son `automatica`, `grados`, `gms`, `radianes` y `pendiente`.
+    pub fn desde_nombre(nombre: &str) -> Option<Self> {
+        match nombre {
+            "automatica" => Some(Self::Automatica),
+            "grados" => Some(Self::Grados),
+            "gms" => Some(Self::GradosMinutosSegundos),
+            "radianes" => Some(Self::Radianes),
+            "pendiente" => Some(Self::Pendiente),
+            _ => None,
+        }
+    }
+
+    /// Deduce la notación de un ángulo escrito en la entrada.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::notacion_angular::NotacionAngular;
+    ///
+    /// assert_eq!(NotacionAngular::deducir("3/4"), NotacionAngular::Pendiente);
+    /// assert_eq!(NotacionAngular::deducir("30:15"), NotacionAngular::GradosMinutosSegundos);
+    /// assert_eq!(NotacionAngular::deducir("0.5rad"), NotacionAngular::Radianes);
+    /// assert_eq!(NotacionAngular::deducir("45"), NotacionAngular::Grados);
+    /// ```
+    pub fn deducir(texto: &str) -> Self {
+        if texto.contains('/') {
+            Self::Pendiente
+        } else if texto.contains(['°', ':']) {
+            Self::GradosMinutosSegundos
+        } else if texto.ends_with("rad") {
+            Self::Radianes
+        } else {
+            Self::Grados
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::area_con_penumbra::RADIO_ANGULAR_DEL_SOL;
+use crate::limites::Limites;
+use crate::notacion_angular::NotacionAngular;
+
+/// Opciones de ejecución que modifican qué se calcula y qué se imprime.
+///
+/// # Campos
+///
+/// * `mostrar_intervalos` - Si además del área se retornan los tramos de sombra fusionados
+/// * `certificado` - Si el área se calcula junto con una cota rigurosa de su error
+/// * `limites` - Los rangos válidos para los valores de la entrada
+/// * `radio_solar` - El radio angular del sol en grados, si se pide la umbra y la penumbra
+/// * `espacial` - Si la entrada describe cajas en tres dimensiones en lugar de flatlanders
+/// * `objetivo` - La longitud en sombra para la que se busca el ángulo del sol, si se pide
+/// * `barrido` - El paso en grados de la tabla de longitudes en sombra por ángulo, si se pide
+/// * `notacion` - La notación con la que está escrito el ángulo del sol en la entrada
+/// * `casos` - Si la entrada tiene varios casos, ver `lector_de_casos::LectorDeCasos`
+/// * `soles` - Los soles que iluminan la escena junto con el de la entrada, si se pidieron
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::opciones::Opciones;
+///
+/// let opciones = Opciones::desde_argumentos(vec!["--intervalos".to_string()]).unwrap();
+/// assert!(opciones.mostrar_intervalos);
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Opciones {
+    pub mostrar_intervalos: bool,
+    pub certificado: bool,
+    pub limites: Limites,
+    pub radio_solar: Option<f64>,
+    pub espacial: bool,
+    pub objetivo: Option<f64>,
+    pub barrido: Option<f64>,
+    pub notacion: NotacionAngular,
+    pub casos: bool,
+    pub soles: Vec<AnguloDelSol>,
+}
+
+impl Opciones {
+    /// Construye las opciones a partir de los argumentos de línea de comandos.
+    ///
+    /// Los argumentos no deben incluir el nombre del programa.
+    ///
+    /// # Argumentos reconocidos
+    ///
+    /// * `--intervalos` - Imprime también los tramos de sombra fusionados
+    /// * `--certificado` - Imprime también la cota de error del área
+    /// * `--cielo-completo` - Acepta ángulos entre 0° y 180°, ver `Limites::cielo_completo`
+    /// * `--penumbra` - Imprime también la umbra y la penumbra, con el radio real del sol
+    /// * `--penumbra=<grados>` - Igual que `--penumbra`, con el radio angular indicado
+    /// * `--espacial` - Lee cajas en tres dimensiones e imprime el área de sus sombras
+    /// * `--objetivo=<longitud>` - Imprime también el ángulo del sol con el que la longitud
+    ///   en sombra es la indicada, ver `busqueda_de_angulo::angulo_para_longitud`
+    /// * `--barrido` - Imprime también la longitud en sombra para cada ángulo entero de
+    ///   los límites, ver `barrido_de_angulos::BarridoDeAngulos`
+    /// * `--barrido=<paso>` - Igual que `--barrido`, avanzando de a `paso` grados
+    /// * `--notacion=<nombre>` - Lee el ángulo en la notación indicada en lugar de
+    ///   deducirla, ver `NotacionAngular::desde_nombre`
+    /// * `--casos` - Lee varios casos e imprime el resultado o el error de cada uno
+    /// * `--soles=<angulo>,<angulo>,...` - Ilumina la escena también con un sol en cada
+    ///   ángulo indicado e imprime la cobertura por fuentes, ver `escena::Escena`
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::ArgumentoInvalido` - Si algún argumento no es reconocido, el radio
+    ///   no es un número entre 0 y 90, el objetivo no es un número finito no negativo, o
+    ///   el paso del barrido no es un número finito positivo, la notación no existe, o
+    ///   algún ángulo de los soles no se puede leer
+    pub fn desde_argumentos<I: IntoIterator<Item = String>>(
+        argumentos: I,
+    ) -> Result<Self, crate::ErrorTipo> {
+        let mut opciones = Opciones::default();
+
+        for argumento in argumentos {
+            match argumento.as_str() {
+                "--intervalos" => opciones.mostrar_intervalos = true,
+                "--certificado" => opciones.certificado = true,
+                "--cielo-completo" => opciones.limites = Limites::cielo_completo(),
+                "--penumbra" => opciones.radio_solar = Some(RADIO_ANGULAR_DEL_SOL),
+                "--espacial" => opciones.espacial = true,
+                "--barrido" => opciones.barrido = Some(1.0),
+                "--casos" => opciones.casos = true,
+                otro if otro.starts_with("--notacion=") => {
+                    opciones.notacion = otro
+                        .strip_prefix("--notacion=")
+                        .and_then(NotacionAngular::desde_nombre)
+                        .ok_or(crate::ErrorTipo::ArgumentoInvalido)?
+                }
+                otro if otro.starts_with("--soles=") => opciones.soles = leer_soles(otro)?,
+                otro if otro.starts_with("--barrido=") => {
+                    opciones.barrido = Some(leer_paso_del_barrido(otro)?)
+                }
+                otro if otro.starts_with("--objetivo=") => {
+                    opciones.objetivo = Some(leer_objetivo(otro)?)
+                }
+                otro if otro.starts_with("--penumbra=") => {
+                    opciones.radio_solar = Some(leer_radio_solar(otro)?)
+                }
+                _ => return Err(crate::ErrorTipo::ArgumentoInvalido),
+            }
+        }
+
+        Ok(opciones)
+    }
+}
+
+/// Lee el radio angular de un argumento `--penumbra=<grados>`.
+fn leer_radio_solar(argumento: &str) -> Result<f64, crate::ErrorTipo> {
+    argumento
+        .strip_prefix("--penumbra=")
+        .and_then(|valor| valor.parse::<f64>().ok())
+        .filter(|radio| (0.0..90.0).contains(radio))
+        .ok_or(crate::ErrorTipo::ArgumentoInvalido)
+}
+
+/// Lee la longitud objetivo de un argumento `--objetivo=<longitud>`.
+fn leer_objetivo(argumento: &str) -> Result<f64, crate::ErrorTipo> {
+    argumento
+        .strip_prefix("--objetivo=")
+        .and_then(|valor| valor.parse::<f64>().ok())
+        .filter(|objetivo| objetivo.is_finite() && *objetivo >= 0.0)
+        .ok_or(crate::ErrorTipo::ArgumentoInvalido)
+}
+
+/// Lee los ángulos de un argumento `--soles=<angulo>,<angulo>,...`, en cualquier notación.
+fn leer_soles(argumento: &str) -> Result<Vec<AnguloDelSol>, crate::ErrorTipo> {
+    argumento
+        .strip_prefix("--soles=")
+        .ok_or(crate::ErrorTipo::ArgumentoInvalido)?
+        .split(',')
+        .map(|texto| AnguloDelSol::leer(texto, NotacionAngular::Automatica))
+        .collect::<Result<_, _>>()
+        .map_err(|_| crate::ErrorTipo::ArgumentoInvalido)
+}
+
+/// Lee el paso en grados de un argumento `--barrido=<paso>`.
+fn leer_paso_del_barrido(argumento: &str) -> Result<f64, crate::ErrorTipo> {
+    argumento
+        .strip_prefix("--barrido=")
+        .and_then(|valor| valor.parse::<f64>().ok())
+        .filter(|paso| paso.is_finite() && *paso > 0.0)
+        .ok_or(crate::ErrorTipo::ArgumentoInvalido)
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argumentos(valores: &[&str]) -> Vec<String> {
+        valores.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_sin_argumentos() {
+        let opciones = Opciones::desde_argumentos(argumentos(&[]));
+        assert_eq!(opciones, Ok(Opciones::default()));
+    }
+
+    #[test]
+    fn test_argumento_intervalos() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--intervalos"]));
+        assert_eq!(
+            opciones,
+            Ok(Opciones {
+                mostrar_intervalos: true,
+                ..Opciones::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_varios_argumentos() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--certificado", "--intervalos"]));
+        assert_eq!(
+            opciones,
+            Ok(Opciones {
+                mostrar_intervalos: true,
+                certificado: true,
+                ..Opciones::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_argumento_cielo_completo() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--cielo-completo"]));
+        assert_eq!(opciones.map(|o| o.limites), Ok(Limites::cielo_completo()));
+    }
+
+    #[test]
+    fn test_argumento_penumbra() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--penumbra"]));
+        assert_eq!(
+            opciones.map(|o| o.radio_solar),
+            Ok(Some(RADIO_ANGULAR_DEL_SOL))
+        );
+
+        let opciones = Opciones::desde_argumentos(argumentos(&["--penumbra=2.5"]));
+        assert_eq!(opciones.map(|o| o.radio_solar), Ok(Some(2.5)));
+    }
+
+    #[test]
+    fn test_argumento_penumbra_invalido() {
+        for argumento in [
+            "--penumbra=",
+            "--penumbra=abc",
+            "--penumbra=-1",
+            "--penumbra=90",
+        ] {
+            let opciones = Opciones::desde_argumentos(argumentos(&[argumento]));
+            assert_eq!(opciones, Err(crate::ErrorTipo::ArgumentoInvalido));
+        }
+    }
+
+    #[test]
+    fn test_argumento_espacial() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--espacial"]));
+        assert_eq!(opciones.map(|o| o.espacial), Ok(true));
+    }
+
+    #[test]
+    fn test_argumento_objetivo() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--objetivo=446.41"]));
+        assert_eq!(opciones.map(|o| o.objetivo), Ok(Some(446.41)));
+
+        for argumento in ["--objetivo=", "--objetivo=-3", "--objetivo=inf"] {
+            let opciones = Opciones::desde_argumentos(argumentos(&[argumento]));
+            assert_eq!(opciones, Err(crate::ErrorTipo::ArgumentoInvalido));
+        }
+    }
+
+    #[test]
+    fn test_argumento_barrido() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--barrido"]));
+        assert_eq!(opciones.map(|o| o.barrido), Ok(Some(1.0)));
+
+        let opciones = Opciones::desde_argumentos(argumentos(&["--barrido=0.5"]));
+        assert_eq!(opciones.map(|o| o.barrido), Ok(Some(0.5)));
+
+        for argumento in ["--barrido=", "--barrido=0", "--barrido=-1"] {
+            let opciones = Opciones::desde_argumentos(argumentos(&[argumento]));
+            assert_eq!(opciones, Err(crate::ErrorTipo::ArgumentoInvalido));
+        }
+    }
+
+    #[test]
+    fn test_argumento_notacion() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--notacion=radianes"]));
+        assert_eq!(opciones.map(|o| o.notacion), Ok(NotacionAngular::Radianes));
+
+        let opciones = Opciones::desde_argumentos(argumentos(&["--notacion=sexagesimal"]));
+        assert_eq!(opciones, Err(crate::ErrorTipo::ArgumentoInvalido));
+    }
+
+    #[test]
+    fn test_argumento_casos() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--casos"]));
+        assert_eq!(opciones.map(|o| o.casos), Ok(true));
+    }
+
+    #[test]
+    fn test_argumento_soles() {
+        let opciones = Opciones::desde_argumentos(argumentos(&["--soles=135,1/2"]));
+        assert_eq!(
+            opciones.map(|o| o.soles),
+            Ok(vec![
+                AnguloDelSol::Grados(135.0),
+                AnguloDelSol::leer("1/2", NotacionAngular::Pendiente).unwrap()
+            ])
+        );
+
+        for argumento in ["--soles=", "--soles=45,", "--soles=norte"] {
+            let opciones = Opciones::desde_argumentos(argumentos(&[argumento]));
+            assert_eq!(opciones, Err(crate::ErrorTipo::ArgumentoInvalido));
+        }
+    }
+
+    #[test]
+    fn test_argumento_invalido() {
+        for argumento in ["--desconocido", "--penumbra2", "penumbra=1"] {
+            let opciones = Opciones::desde_argumentos(argumentos(&[argumento]));
+            assert_eq!(opciones, Err(crate::ErrorTipo::ArgumentoInvalido));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::PRECISION_IGUALDAD_FLOAT;
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{
+    Intervalo, calcular_huecos_iluminados, fusionar_intervalos, intersecar_intervalos,
+    longitud_total,
+};
+
+/// Busca dónde ubicar nuevos flatlanders para sombrear lo más posible una ventana del camino.
+///
+/// Guarda los huecos iluminados de la ventana, con la longitud acumulada de los huecos
+/// anteriores a cada uno para medir en tiempo logarítmico cuánto sol tapa una sombra nueva.
+///
+/// # Campos
+///
+/// * `ventana` - El tramo del camino que interesa sombrear
+/// * `sombras` - Los tramos de sombra fusionados de la escena actual
+/// * `huecos` - Los tramos iluminados de la ventana, ordenados
+/// * `acumulado` - La longitud total de los huecos anteriores a cada hueco, más el total al final
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::optimizador_de_sombra::OptimizadorDeSombra;
+///
+/// // Sombras de [0, 10] y [25, 35]: en [0, 40] quedan al sol [10, 25] y [35, 40]
+/// let flatlanders = vec![Flatlander::new(0, 10, 45), Flatlander::new(25, 10, 45)];
+/// let optimizador = OptimizadorDeSombra::new(&flatlanders, Intervalo { inicio: 0.0, fin: 40.0 });
+///
+/// let theta = AnguloDelSol::Grados(45.0);
+/// let plan = optimizador.ubicar(1, 15, &theta);
+/// assert_eq!(plan.posiciones.len(), 1);
+/// assert!((plan.posiciones[0] - 10.0).abs() < 1e-9);
+/// assert!((plan.cubierta - 35.0).abs() < 1e-9);
+///
+/// assert_eq!(optimizador.minimo_para_cubrir(5, &theta), Some(4));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct OptimizadorDeSombra {
+    ventana: Intervalo,
+    sombras: Vec<Intervalo>,
+    huecos: Vec<Intervalo>,
+    acumulado: Vec<f64>,
+}
+
+impl OptimizadorDeSombra {
+    /// Prepara el optimizador con los huecos iluminados que deja la escena en la ventana.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders que ya están en el camino
+    /// * `ventana` - El tramo del camino que interesa sombrear
+    pub fn new(flatlanders: &[Flatlander], ventana: Intervalo) -> Self {
+        let mut sombras: Vec<Intervalo> = flatlanders.iter().map(Flatlander::sombra).collect();
+        let sombras = fusionar_intervalos(&mut sombras);
+        let huecos = calcular_huecos_iluminados(&sombras, &ventana);
+
+        let mut acumulado = vec![0.0];
+        for hueco in huecos.iter() {
+            acumulado.push(acumulado[acumulado.len() - 1] + (hueco.fin - hueco.inicio));
+        }
+
+        Self {
+            ventana,
+            sombras,
+            huecos,
+            acumulado,
+        }
+    }
+
+    /// Retorna la longitud en sombra de la ventana antes de agregar flatlanders.
+    pub fn cubierta(&self) -> f64 {
+        let iluminada = self.acumulado[self.acumulado.len() - 1];
+        (self.ventana.fin - self.ventana.inicio).max(0.0) - iluminada
+    }
+
+    /// Busca las posiciones de `k` postes nuevos de altura `h` que más sombra agregan a la ventana.
+    ///
+    /// Hay una solución óptima en la que las sombras nuevas no se superponen y forman
+    /// cadenas pegadas, donde cada cadena tiene algún extremo de sombra sobre un borde
+    /// de hueco. Por eso alcanza con probar como inicio de sombra cada borde de hueco
+    /// corrido un número entero de longitudes de sombra, y elegir con programación
+    /// dinámica las mejores sombras sin superposición. Con `g` huecos el costo es
+    /// `O(g k^2 log(g k))`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `k` - La cantidad de postes nuevos
+    /// * `h` - La altura de los postes nuevos
+    /// * `theta` - El ángulo del sol, el mismo con el que se creó la escena
+    pub fn ubicar(&self, k: usize, h: u32, theta: &AnguloDelSol) -> PlanDeSombra {
+        let l = theta.longitud_de_sombra(h as f64);
+        let largo = l.abs();
+        if largo == 0.0 || k == 0 {
+            return self.plan_con_inicios(Vec::new(), l);
+        }
+
+        let candidatos = self.candidatos(k, largo);
+        let inicios = self.elegir_inicios(&candidatos, k, largo);
+        self.plan_con_inicios(inicios, l)
+    }
+
+    /// Calcula la menor cantidad de postes de altura `h` que dejan toda la ventana en sombra.
+    ///
+    /// Recorre los huecos de izquierda a derecha poniendo cada sombra nueva justo donde
+    /// empieza la parte todavía iluminada, lo que es óptimo para cubrir con tramos de
+    /// igual longitud.
+    ///
+    /// # Retorna
+    ///
+    /// `None` si quedan huecos y el sol está en la vertical, porque los postes no dan sombra.
+    pub fn minimo_para_cubrir(&self, h: u32, theta: &AnguloDelSol) -> Option<usize> {
+        let largo = theta.longitud_de_sombra(h as f64).abs();
+        if self.huecos.is_empty() {
+            return Some(0);
+        }
+        if largo == 0.0 {
+            return None;
+        }
+
+        let mut cantidad = 0;
+        let mut cubierto_hasta = f64::NEG_INFINITY;
+        for hueco in self.huecos.iter() {
+            let cursor = hueco.inicio.max(cubierto_hasta);
+            if cursor < hueco.fin {
+                let necesarios = ((hueco.fin - cursor) / largo).ceil();
+                cantidad += necesarios as usize;
+                cubierto_hasta = cursor + necesarios * largo;
+            }
+        }
+        Some(cantidad)
+    }
+
+    /// Retorna la longitud iluminada de la ventana que queda en `(-inf, x]`.
+    fn iluminado_hasta(&self, x: f64) -> f64 {
+        let i = self.huecos.partition_point(|hueco| hueco.fin <= x);
+        let parcial = self
+            .huecos
+            .get(i)
+            .map_or(0.0, |hueco| (x - hueco.inicio).max(0.0));
+        self.acumulado[i] + parcial
+    }
+
+    /// Retorna los inicios de sombra a probar: cada borde de hueco corrido entre `-k` y
+    /// `k` longitudes de sombra, ordenados y sin repetir.
+    fn candidatos(&self, k: usize, largo: f64) -> Vec<f64> {
+        let pasos = -(k as i64)..=k as i64;
+        let mut candidatos: Vec<f64> = self
+            .huecos
+            .iter()
+            .flat_map(|hueco| [hueco.inicio, hueco.fin])
+            .flat_map(|borde| pasos.clone().map(move |t| borde + t as f64 * largo))
+            .collect();
+        candidatos.sort_by(f64::total_cmp);
+        candidatos.dedup();
+        candidatos
+    }
+
+    /// Elige hasta `k` inicios entre los candidatos, sin que sus sombras se superpongan,
+    /// maximizando la longitud iluminada que tapan.
+    fn elegir_inicios(&self, candidatos: &[f64], k: usize, largo: f64) -> Vec<f64> {
+        let aporte = |s: f64| self.iluminado_hasta(s + largo) - self.iluminado_hasta(s);
+        // `anterior[i]` es cuántos candidatos terminan su sombra antes del candidato `i`
+        let anterior: Vec<usize> = candidatos
+            .iter()
+            .map(|&c| {
+                let limite = c - largo + PRECISION_IGUALDAD_FLOAT * c.abs().max(1.0);
+                candidatos.partition_point(|&otro| otro <= limite)
+            })
+            .collect();
+
+        // `mejor[j][i]` es lo más que se tapa con `j` sombras entre los primeros `i` candidatos
+        let mut mejor = vec![vec![0.0; candidatos.len() + 1]; k + 1];
+        for j in 1..=k {
+            for (i, &c) in candidatos.iter().enumerate() {
+                let con_c = aporte(c) + mejor[j - 1][anterior[i]];
+                mejor[j][i + 1] = mejor[j][i].max(con_c);
+            }
+        }
+
+        reconstruir_inicios(&mejor, candidatos, &anterior)
+    }
+
+    /// Arma el plan con las sombras nuevas que empiezan en `inicios`, midiendo la sombra
+    /// final de la ventana con la unión de las sombras viejas y las nuevas.
+    fn plan_con_inicios(&self, inicios: Vec<f64>, l: f64) -> PlanDeSombra {
+        let mut sombras: Vec<Intervalo> = inicios
+            .iter()
+            .map(|&inicio| Intervalo {
+                inicio,
+                fin: inicio + l.abs(),
+            })
+            .chain(self.sombras.iter().copied())
+            .collect();
+        let unidas = fusionar_intervalos(&mut sombras);
+        let cubierta = longitud_total(&intersecar_intervalos(&unidas, &[self.ventana]));
+
+        // Con el sol al este la sombra queda al oeste del poste
+        let posiciones = inicios.iter().map(|inicio| inicio - l.min(0.0)).collect();
+        PlanDeSombra {
+            posiciones,
+            cubierta,
+        }
+    }
+}
+
+/// Recorre la tabla de la programación dinámica desde el final para recuperar los
+/// inicios elegidos, de menor a mayor.
+fn reconstruir_inicios(mejor: &[Vec<f64>], candidatos: &[f64], anterior: &[usize]) -> Vec<f64> {
+    let mut inicios = Vec::new();
+    let (mut j, mut i) = (mejor.len() - 1, candidatos.len());
+    while j > 0 && i > 0 {
+        if mejor[j][i] > mejor[j][i - 1] {
+            inicios.push(candidatos[i - 1]);
+            i = anterior[i - 1];
+            j -= 1;
+        } else {
+            i -= 1;
+        }
+    }
+    inicios.reverse();
+    inicios
+}
+
+/// Dónde ubicar nuevos flatlanders y cuánta sombra se logra con ellos.
+///
+/// # Campos
+///
+/// * `posiciones` - Las posiciones de los nuevos flatlanders, ordenadas. Puede tener menos
+///   posiciones que flatlanders pedidos si los demás ya no agregan sombra
+/// * `cubierta` - La longitud en sombra dentro de la ventana, contando la escena y los nuevos
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::optimizador_de_sombra::PlanDeSombra;
+///
+/// let plan = PlanDeSombra {
+///     posiciones: vec![10.0, 30.0],
+///     cubierta: 35.0,
+/// };
+/// assert_eq!(plan.posiciones.len(), 2);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct PlanDeSombra {
+    pub posiciones: Vec<f64>,
+    pub cubierta: f64,
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ventana(inicio: f64, fin: f64) -> Intervalo {
+        Intervalo { inicio, fin }
+    }
+
+    #[test]
+    fn test_sin_flatlanders_nuevos() {
+        let flatlanders = vec![Flatlander::new(0, 10, 45)];
+        let optimizador = OptimizadorDeSombra::new(&flatlanders, ventana(0.0, 20.0));
+
+        let plan = optimizador.ubicar(0, 10, &AnguloDelSol::Grados(45.0));
+        assert!(plan.posiciones.is_empty());
+        assert!((plan.cubierta - 10.0).abs() < 1e-9);
+        assert!((optimizador.cubierta() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cadena_pegada_al_final_del_hueco() {
+        // Un hueco de [0, 25]: la mejor ubicación de dos sombras de 10 tapa 20
+        let optimizador = OptimizadorDeSombra::new(&[], ventana(0.0, 25.0));
+
+        let plan = optimizador.ubicar(2, 10, &AnguloDelSol::Grados(45.0));
+        assert_eq!(plan.posiciones.len(), 2);
+        assert!((plan.cubierta - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mejor_que_la_estrategia_golosa() {
+        // Huecos de [0, 6] y [8, 14] con sombras de 8: una sola sombra tapa a lo sumo 6,
+        // pero dos sombras pueden tapar los dos huecos completos
+        let flatlanders = vec![Flatlander::new(6, 2, 45), Flatlander::new(14, 10, 45)];
+        let optimizador = OptimizadorDeSombra::new(&flatlanders, ventana(0.0, 24.0));
+
+        let plan = optimizador.ubicar(2, 8, &AnguloDelSol::Grados(45.0));
+        assert!((plan.cubierta - 24.0).abs() < 1e-9);
+        assert_eq!(
+            optimizador.minimo_para_cubrir(8, &AnguloDelSol::Grados(45.0)),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_sol_al_este_corre_las_posiciones() {
+        // Con la sombra hacia el oeste, el poste va en el extremo derecho de su sombra
+        let optimizador = OptimizadorDeSombra::new(&[], ventana(0.0, 10.0));
+
+        let plan = optimizador.ubicar(1, 10, &AnguloDelSol::Grados(135.0));
+        assert_eq!(plan.posiciones.len(), 1);
+        assert!((plan.posiciones[0] - 10.0).abs() < 1e-9);
+        assert!((plan.cubierta - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_minimo_con_sol_vertical() {
+        let optimizador = OptimizadorDeSombra::new(&[], ventana(0.0, 10.0));
+
+        assert_eq!(
+            optimizador.minimo_para_cubrir(10, &AnguloDelSol::Grados(90.0)),
+            None
+        );
+        let cubierta = OptimizadorDeSombra::new(&[Flatlander::new(0, 20, 45)], ventana(0.0, 10.0));
+        assert_eq!(
+            cubierta.minimo_para_cubrir(10, &AnguloDelSol::Grados(90.0)),
+            Some(0)
+        );
+    }
+}
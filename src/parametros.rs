@@ -1,33 +1,33 @@
-/// Rango válido para el ángulo theta del sol
-const RANGO_THETA: std::ops::RangeInclusive<u32> = 10..=80;
-
-/// Rango válido para el número de flatlanders
-const RANGO_N: std::ops::RangeInclusive<u32> = 1..=100_000;
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::limites::Limites;
+use crate::notacion_angular::NotacionAngular;
 
 /// Estructura para almacenar los parámetros de entrada
 ///
 /// # Campos
 ///
-/// * `theta` - Ángulo del sol en grados
+/// * `theta` - Ángulo del sol, en grados (que pueden no ser enteros) o como pendiente exacta
 /// * `n` - Número de flatlanders a procesar
 ///
 /// # Ejemplo
 ///
 /// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
 /// use el_rompecabezas_de_las_sombreas::parametros::Parametros;
 ///
 /// let parametros = Parametros {
-///     theta: 45,
+///     theta: AnguloDelSol::Grados(45.0),
 ///     n: 100,
 /// };
 /// ```
 pub struct Parametros {
-    pub theta: u32,
+    pub theta: AnguloDelSol,
     pub n: u32,
 }
 
 impl Parametros {
-    /// Lee y valida los parámetros desde la primera línea de entrada.
+    /// Lee y valida los parámetros desde la primera línea de entrada, con los límites
+    /// del enunciado (ver `Limites::concurso`).
     ///
     /// # Argumentos
     ///
@@ -43,7 +43,7 @@ impl Parametros {
     /// * `ErrorTipo::LineaFaltante` - No hay línea de entrada disponible
     /// * `ErrorTipo::IO` - Error de entrada/salida al leer
     /// * `ErrorTipo::ValorFaltante` - La línea no contiene exactamente 2 valores
-    /// * `ErrorTipo::NumeroInvalido` - Los valores no son números enteros válidos
+    /// * `ErrorTipo::NumeroInvalido` - `n` no es un entero válido o `theta` no es un ángulo válido
     /// * `ErrorTipo::FueraDeRango` - Los valores están fuera de los rangos permitidos
     ///
     /// # Ejemplo
@@ -57,11 +57,85 @@ impl Parametros {
     /// let mut lineas = reader.lines();
     ///
     /// let parametros = Parametros::leer_desde_stdin(&mut lineas).unwrap();
-    /// assert_eq!(parametros.theta, 45);
+    /// assert_eq!(parametros.theta.grados(), 45.0);
     /// assert_eq!(parametros.n, 100);
     /// ```
     pub fn leer_desde_stdin<R: std::io::BufRead>(
         lineas: &mut std::io::Lines<R>,
+    ) -> Result<Self, crate::ErrorTipo> {
+        Self::leer_desde_stdin_con_limites(lineas, &Limites::concurso())
+    }
+
+    /// Lee y valida los parámetros desde la primera línea de entrada, con los límites dados.
+    ///
+    /// # Argumentos
+    ///
+    /// * `lineas` - Un iterador mutable sobre las líneas de entrada
+    /// * `limites` - Los rangos válidos para `theta` y `n`
+    ///
+    /// # Errores
+    ///
+    /// Los mismos que `leer_desde_stdin`.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use std::io::{BufReader, BufRead};
+    /// use el_rompecabezas_de_las_sombreas::limites::Limites;
+    /// use el_rompecabezas_de_las_sombreas::parametros::Parametros;
+    ///
+    /// let input = "135 1\n";
+    /// let mut lineas = BufReader::new(input.as_bytes()).lines();
+    ///
+    /// let parametros =
+    ///     Parametros::leer_desde_stdin_con_limites(&mut lineas, &Limites::cielo_completo());
+    /// assert_eq!(parametros.map(|p| p.theta.grados()), Ok(135.0));
+    /// ```
+    pub fn leer_desde_stdin_con_limites<R: std::io::BufRead>(
+        lineas: &mut std::io::Lines<R>,
+        limites: &Limites,
+    ) -> Result<Self, crate::ErrorTipo> {
+        Self::leer_desde_stdin_con_notacion(lineas, limites, NotacionAngular::Automatica)
+    }
+
+    /// Lee y valida los parámetros con los límites dados y el ángulo en la notación indicada.
+    ///
+    /// El ángulo puede escribirse en grados decimales, en grados, minutos y segundos, en
+    /// radianes o como una pendiente exacta `subida/avance`, ver `NotacionAngular`. Con
+    /// `NotacionAngular::Automatica` la notación se deduce del texto. En todos los casos
+    /// el ángulo, pasado a grados, tiene que estar en el rango de `theta` de los límites.
+    ///
+    /// # Argumentos
+    ///
+    /// * `lineas` - Un iterador mutable sobre las líneas de entrada
+    /// * `limites` - Los rangos válidos para `theta` y `n`
+    /// * `notacion` - La notación con la que está escrito el ángulo
+    ///
+    /// # Errores
+    ///
+    /// Los mismos que `leer_desde_stdin`.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use std::io::{BufReader, BufRead};
+    /// use el_rompecabezas_de_las_sombreas::limites::Limites;
+    /// use el_rompecabezas_de_las_sombreas::notacion_angular::NotacionAngular;
+    /// use el_rompecabezas_de_las_sombreas::parametros::Parametros;
+    ///
+    /// let mut lineas = BufReader::new("0.5 1\n".as_bytes()).lines();
+    /// let parametros = Parametros::leer_desde_stdin_con_notacion(
+    ///     &mut lineas,
+    ///     &Limites::concurso(),
+    ///     NotacionAngular::Radianes,
+    /// )
+    /// .unwrap();
+    /// assert!((parametros.theta.grados() - 28.6479).abs() < 1e-4);
+    /// ```
+    pub fn leer_desde_stdin_con_notacion<R: std::io::BufRead>(
+        lineas: &mut std::io::Lines<R>,
+        limites: &Limites,
+        notacion: NotacionAngular,
     ) -> Result<Self, crate::ErrorTipo> {
         // Leer la primera línea
         let primera_linea = match lineas.next() {
@@ -78,21 +152,16 @@ impl Parametros {
             return Err(crate::ErrorTipo::ValorFaltante);
         }
 
-        // Si no se puede parsear theta (o esta fuera de rango) retorno error
-        let theta: u32 = match tokens[0].trim().parse() {
-            Ok(v) => {
-                if !RANGO_THETA.contains(&v) {
-                    return Err(crate::ErrorTipo::FueraDeRango);
-                }
-                v
-            }
-            Err(_) => return Err(crate::ErrorTipo::NumeroInvalido),
-        };
+        // Si no se puede interpretar theta (o esta fuera de rango) retorno error
+        let theta = AnguloDelSol::leer(tokens[0], notacion)?;
+        if !theta.esta_en(&limites.theta) {
+            return Err(crate::ErrorTipo::FueraDeRango);
+        }
 
         // Si no se puede parsear n (o esta fuera de rango) retorno error
         let n: u32 = match tokens[1].trim().parse() {
             Ok(v) => {
-                if !RANGO_N.contains(&v) {
+                if !limites.n.contains(&v) {
                     return Err(crate::ErrorTipo::FueraDeRango);
                 }
                 v
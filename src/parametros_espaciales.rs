@@ -0,0 +1,139 @@
+use crate::limites::Limites;
+
+/// Rango válido para el azimut del sol, en grados
+pub const RANGO_AZIMUT: std::ops::RangeInclusive<u32> = 0..=359;
+
+/// Parámetros de entrada del problema en tres dimensiones.
+///
+/// # Campos
+///
+/// * `azimut` - La dirección hacia el sol en grados, medida desde el este en sentido antihorario
+/// * `elevacion` - El ángulo del sol sobre el horizonte en grados
+/// * `n` - Número de cajas a procesar
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::parametros_espaciales::ParametrosEspaciales;
+///
+/// let parametros = ParametrosEspaciales {
+///     azimut: 180,
+///     elevacion: 45,
+///     n: 100,
+/// };
+/// ```
+pub struct ParametrosEspaciales {
+    pub azimut: u32,
+    pub elevacion: u32,
+    pub n: u32,
+}
+
+impl ParametrosEspaciales {
+    /// Lee y valida los parámetros desde la primera línea de entrada.
+    ///
+    /// La elevación se valida con el rango de `theta` y la cantidad de cajas con el de `n`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `lineas` - Un iterador mutable sobre las líneas de entrada
+    /// * `limites` - Los rangos válidos para la elevación y `n`
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::LineaFaltante` - No hay línea de entrada disponible
+    /// * `ErrorTipo::IO` - Error de entrada/salida al leer
+    /// * `ErrorTipo::ValorFaltante` - La línea no contiene exactamente 3 valores
+    /// * `ErrorTipo::NumeroInvalido` - Los valores no son números enteros válidos
+    /// * `ErrorTipo::FueraDeRango` - Los valores están fuera de los rangos permitidos
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use std::io::{BufReader, BufRead};
+    /// use el_rompecabezas_de_las_sombreas::limites::Limites;
+    /// use el_rompecabezas_de_las_sombreas::parametros_espaciales::ParametrosEspaciales;
+    ///
+    /// let mut lineas = BufReader::new("270 30 2\n".as_bytes()).lines();
+    /// let parametros =
+    ///     ParametrosEspaciales::leer_desde_stdin_con_limites(&mut lineas, &Limites::concurso())
+    ///         .unwrap();
+    /// assert_eq!((parametros.azimut, parametros.elevacion, parametros.n), (270, 30, 2));
+    /// ```
+    pub fn leer_desde_stdin_con_limites<R: std::io::BufRead>(
+        lineas: &mut std::io::Lines<R>,
+        limites: &Limites,
+    ) -> Result<Self, crate::ErrorTipo> {
+        let primera_linea = match lineas.next() {
+            Some(Ok(linea)) => linea,
+            Some(Err(_)) => return Err(crate::ErrorTipo::IO),
+            None => return Err(crate::ErrorTipo::LineaFaltante),
+        };
+
+        // Esperamos exactamente 3 tokens: azimut, elevación y n
+        let tokens: Vec<&str> = primera_linea.split_whitespace().collect();
+        if tokens.len() != 3 {
+            return Err(crate::ErrorTipo::ValorFaltante);
+        }
+
+        Ok(ParametrosEspaciales {
+            azimut: leer_valor(tokens[0], &RANGO_AZIMUT)?,
+            elevacion: leer_valor(tokens[1], &limites.theta)?,
+            n: leer_valor(tokens[2], &limites.n)?,
+        })
+    }
+}
+
+/// Interpreta un token como entero y valida que esté en el rango dado.
+///
+/// # Errores
+///
+/// * `ErrorTipo::NumeroInvalido` - Si el token no es un número entero válido
+/// * `ErrorTipo::FueraDeRango` - Si el número está fuera del rango
+pub(crate) fn leer_valor(
+    token: &str,
+    rango: &std::ops::RangeInclusive<u32>,
+) -> Result<u32, crate::ErrorTipo> {
+    match token.trim().parse() {
+        Ok(v) if rango.contains(&v) => Ok(v),
+        Ok(_) => Err(crate::ErrorTipo::FueraDeRango),
+        Err(_) => Err(crate::ErrorTipo::NumeroInvalido),
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+
+    fn leer(entrada: &str) -> Result<ParametrosEspaciales, crate::ErrorTipo> {
+        let mut lineas = std::io::BufReader::new(entrada.as_bytes()).lines();
+        ParametrosEspaciales::leer_desde_stdin_con_limites(&mut lineas, &Limites::concurso())
+    }
+
+    #[test]
+    fn test_parametros_invalidos() {
+        assert!(matches!(
+            leer("180 45\n"),
+            Err(crate::ErrorTipo::ValorFaltante)
+        ));
+        assert!(matches!(
+            leer("360 45 1\n"),
+            Err(crate::ErrorTipo::FueraDeRango)
+        ));
+        assert!(matches!(
+            leer("180 5 1\n"),
+            Err(crate::ErrorTipo::FueraDeRango)
+        ));
+        assert!(matches!(
+            leer("180 x 1\n"),
+            Err(crate::ErrorTipo::NumeroInvalido)
+        ));
+        assert!(matches!(leer(""), Err(crate::ErrorTipo::LineaFaltante)));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::flatlanders::Flatlander;
+use crate::intervalos::Intervalo;
+
+/// Perfil de oscuridad a lo largo del camino para flatlanders translúcidos.
+///
+/// Cada sombra deja pasar una fracción `1 - opacidad` de la luz y las sombras superpuestas
+/// se combinan multiplicando esas fracciones. La oscuridad de un tramo es `1` menos la luz
+/// que llega a él, por lo que vale `0` al sol y `1` bajo una sombra opaca.
+///
+/// # Campos
+///
+/// * `tramos` - Los tramos con oscuridad positiva, ordenados y disjuntos, junto con su oscuridad
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::perfil_de_oscuridad::PerfilDeOscuridad;
+///
+/// let flatlanders = vec![
+///     Flatlander::new(0, 10, 45).con_opacidad(0.5).unwrap(), // Sombra de [0, 10]
+///     Flatlander::new(5, 10, 45).con_opacidad(0.5).unwrap(), // Sombra de [5, 15]
+/// ];
+///
+/// let perfil = PerfilDeOscuridad::new(&flatlanders);
+/// // [0, 5] y [10, 15] con oscuridad 0.5, [5, 10] con oscuridad 1 - 0.5 * 0.5 = 0.75
+/// assert!((perfil.oscuridad_integrada() - 8.75).abs() < 1e-9);
+/// assert!((perfil.longitud_mas_oscura_que(0.6) - 5.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct PerfilDeOscuridad {
+    tramos: Vec<(Intervalo, f64)>,
+}
+
+impl PerfilDeOscuridad {
+    /// Calcula el perfil barriendo los extremos de las sombras en orden.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders de la escena, cada uno con su opacidad
+    pub fn new(flatlanders: &[Flatlander]) -> Self {
+        // Cada evento es (posición, opacidad, si empieza o termina una sombra)
+        let mut eventos: Vec<(f64, f64, bool)> = flatlanders
+            .iter()
+            .flat_map(|f| {
+                let sombra = f.sombra();
+                [
+                    (sombra.inicio, f.opacidad, true),
+                    (sombra.fin, f.opacidad, false),
+                ]
+            })
+            .collect();
+        eventos.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut perfil = Self { tramos: Vec::new() };
+        perfil.barrer(&eventos);
+        perfil
+    }
+
+    /// Recorre los eventos llevando la luz que llega a cada tramo entre dos eventos.
+    ///
+    /// Las sombras opacas se cuentan aparte, y las translúcidas se acumulan como suma de
+    /// logaritmos, para poder quitarlas sin dividir por cero.
+    fn barrer(&mut self, eventos: &[(f64, f64, bool)]) {
+        let (mut activas, mut opacas, mut log_luz) = (0_usize, 0_usize, 0.0_f64);
+        let mut anterior = eventos.first().map_or(0.0, |evento| evento.0);
+
+        for &(posicion, opacidad, es_inicio) in eventos {
+            let oscuridad = if opacas > 0 { 1.0 } else { -log_luz.exp_m1() };
+            if posicion > anterior && activas > 0 && oscuridad > 0.0 {
+                let tramo = Intervalo {
+                    inicio: anterior,
+                    fin: posicion,
+                };
+                self.tramos.push((tramo, oscuridad));
+            }
+            anterior = posicion;
+
+            let signo = if es_inicio { 1.0 } else { -1.0 };
+            if opacidad >= 1.0 {
+                opacas = if es_inicio { opacas + 1 } else { opacas - 1 };
+            } else {
+                log_luz += signo * (-opacidad).ln_1p();
+            }
+            activas = if es_inicio { activas + 1 } else { activas - 1 };
+            // Sin sombras activas volvemos a cero, para no arrastrar errores de redondeo
+            if activas == 0 {
+                log_luz = 0.0;
+            }
+        }
+    }
+
+    /// Retorna los tramos con oscuridad positiva junto con su oscuridad.
+    pub fn tramos(&self) -> &[(Intervalo, f64)] {
+        &self.tramos
+    }
+
+    /// Retorna la oscuridad integrada a lo largo del camino.
+    ///
+    /// Con flatlanders opacos coincide con el área que calcula `calcular_area`.
+    pub fn oscuridad_integrada(&self) -> f64 {
+        self.tramos
+            .iter()
+            .map(|(tramo, oscuridad)| (tramo.fin - tramo.inicio) * oscuridad)
+            .sum()
+    }
+
+    /// Retorna la longitud del camino cuya oscuridad supera estrictamente el umbral.
+    ///
+    /// # Argumentos
+    ///
+    /// * `umbral` - La oscuridad mínima, entre 0 y 1, que se debe superar
+    pub fn longitud_mas_oscura_que(&self, umbral: f64) -> f64 {
+        self.tramos
+            .iter()
+            .filter(|(_, oscuridad)| *oscuridad > umbral)
+            .map(|(tramo, _)| tramo.fin - tramo.inicio)
+            .sum()
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::angulo_del_sol::AnguloDelSol;
+    use crate::flatlanders::crear_intervalo_de_flatlanders;
+    use crate::intervalos::calcular_area;
+    use crate::modelo_de_luz::ModeloDeLuz;
+
+    fn translucido(x: u32, h: u32, opacidad: f64) -> Flatlander {
+        Flatlander::new(x, h, 45).con_opacidad(opacidad).unwrap()
+    }
+
+    #[test]
+    fn test_perfil_vacio() {
+        let perfil = PerfilDeOscuridad::new(&[]);
+        assert!(perfil.tramos().is_empty());
+        assert_eq!(perfil.oscuridad_integrada(), 0.0);
+    }
+
+    #[test]
+    fn test_flatlanders_opacos_coinciden_con_calcular_area() {
+        let flatlanders = vec![
+            Flatlander::new(50, 150, 30),
+            Flatlander::new(0, 100, 30),
+            Flatlander::new(100, 200, 30),
+            Flatlander::new(2000, 10, 30),
+        ];
+        let sol = ModeloDeLuz::Sol {
+            theta: AnguloDelSol::Grados(30.0),
+        };
+        let area = calcular_area(&mut crear_intervalo_de_flatlanders(&flatlanders, &sol).unwrap());
+
+        let perfil = PerfilDeOscuridad::new(&flatlanders);
+        assert!((perfil.oscuridad_integrada() - area).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((perfil.longitud_mas_oscura_que(0.99) - area).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_opaco_dentro_de_translucido() {
+        let flatlanders = vec![translucido(0, 20, 0.25), Flatlander::new(5, 5, 45)];
+        let perfil = PerfilDeOscuridad::new(&flatlanders);
+
+        // [0, 5] y [10, 20] con 0.25, [5, 10] opaco
+        assert!((perfil.oscuridad_integrada() - (15.0 * 0.25 + 5.0)).abs() < 1e-9);
+        assert!((perfil.longitud_mas_oscura_que(0.5) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transparente_no_oscurece() {
+        let flatlanders = vec![translucido(0, 10, 0.0), translucido(20, 10, 0.5)];
+        let perfil = PerfilDeOscuridad::new(&flatlanders);
+
+        assert_eq!(perfil.tramos().len(), 1);
+        assert!((perfil.oscuridad_integrada() - 5.0).abs() < 1e-9);
+    }
+}
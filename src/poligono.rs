@@ -0,0 +1,175 @@
+use crate::intervalos::Intervalo;
+
+/// Polígono convexo en el plano, con los vértices en sentido antihorario.
+///
+/// # Campos
+///
+/// * `vertices` - Los vértices `(x, y)` del polígono, sin puntos repetidos ni alineados
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::poligono::Poligono;
+///
+/// // El punto interior (1, 1) no forma parte de la envolvente
+/// let cuadrado =
+///     Poligono::envolvente_convexa(vec![(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0)]);
+///
+/// assert_eq!(cuadrado.vertices().len(), 4);
+/// assert_eq!(cuadrado.area(), 4.0);
+/// assert_eq!(cuadrado.corte_vertical(0.5), Some(Intervalo { inicio: 0.0, fin: 2.0 }));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Poligono {
+    vertices: Vec<(f64, f64)>,
+}
+
+impl Poligono {
+    /// Calcula la envolvente convexa de los puntos dados con el algoritmo de la cadena
+    /// monótona de Andrew.
+    ///
+    /// # Argumentos
+    ///
+    /// * `puntos` - Los puntos a envolver, en cualquier orden
+    pub fn envolvente_convexa(mut puntos: Vec<(f64, f64)>) -> Self {
+        puntos.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        puntos.dedup();
+        if puntos.len() < 3 {
+            return Self { vertices: puntos };
+        }
+
+        // Cadena inferior de izquierda a derecha y superior de derecha a izquierda. El
+        // último punto de cada cadena es el primero de la otra, así que se descarta
+        let mut vertices = cadena_convexa(puntos.iter().copied());
+        let mut superior = cadena_convexa(puntos.iter().rev().copied());
+        vertices.pop();
+        superior.pop();
+        vertices.extend(superior);
+
+        Self { vertices }
+    }
+
+    /// Retorna los vértices del polígono en sentido antihorario.
+    pub fn vertices(&self) -> &[(f64, f64)] {
+        &self.vertices
+    }
+
+    /// Retorna los lados del polígono como pares de vértices consecutivos.
+    pub fn lados(&self) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+        let siguientes = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(siguientes.copied())
+    }
+
+    /// Retorna el área del polígono, con la fórmula del área de Gauss.
+    pub fn area(&self) -> f64 {
+        self.lados()
+            .map(|(p, q)| p.0 * q.1 - q.0 * p.1)
+            .sum::<f64>()
+            / 2.0
+    }
+
+    /// Retorna el rango de `x` que ocupa el polígono.
+    pub fn rango_x(&self) -> Intervalo {
+        let xs = self.vertices.iter().map(|v| v.0);
+        Intervalo {
+            inicio: xs.clone().fold(f64::INFINITY, f64::min),
+            fin: xs.fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Retorna el tramo que ocupa el polígono sobre la recta vertical en `x`, si la toca.
+    pub fn corte_vertical(&self, x: f64) -> Option<Intervalo> {
+        let (mut inicio, mut fin) = (f64::INFINITY, f64::NEG_INFINITY);
+        for (p, q) in self.lados() {
+            if x < p.0.min(q.0) || x > p.0.max(q.0) {
+                continue;
+            }
+            // Un lado vertical aporta sus dos extremos, y uno inclinado el punto de corte
+            let alturas = if p.0 == q.0 {
+                [p.1, q.1]
+            } else {
+                let y = p.1 + (x - p.0) * (q.1 - p.1) / (q.0 - p.0);
+                [y, y]
+            };
+            inicio = inicio.min(alturas[0].min(alturas[1]));
+            fin = fin.max(alturas[0].max(alturas[1]));
+        }
+
+        // Si ningún lado llega hasta `x`, la recta no toca al polígono
+        (inicio <= fin).then_some(Intervalo { inicio, fin })
+    }
+}
+
+/// Arma la cadena de puntos que siempre gira en sentido antihorario, descartando los
+/// que quedan del lado de adentro.
+fn cadena_convexa(puntos: impl Iterator<Item = (f64, f64)>) -> Vec<(f64, f64)> {
+    let mut cadena: Vec<(f64, f64)> = Vec::new();
+    for punto in puntos {
+        while let [.., a, b] = cadena[..]
+            && giro(a, b, punto) <= 0.0
+        {
+            cadena.pop();
+        }
+        cadena.push(punto);
+    }
+    cadena
+}
+
+/// Producto cruzado de `b - a` y `c - a`: positivo si `a`, `b`, `c` giran en sentido antihorario.
+fn giro(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envolvente_de_un_hexagono() {
+        // Rectángulo de 2x1 desplazado en (3, 1): su envolvente es un hexágono
+        let puntos = vec![
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (0.0, 1.0),
+            (3.0, 1.0),
+            (5.0, 1.0),
+            (5.0, 2.0),
+            (3.0, 2.0),
+        ];
+        let hexagono = Poligono::envolvente_convexa(puntos);
+
+        assert_eq!(hexagono.vertices().len(), 6);
+        // El rectángulo mide 2 y al barrerlo por d = (3, 1) suma |d x (2, 0)| + |d x (0, 1)|
+        assert!((hexagono.area() - 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_envolvente_degenerada() {
+        let segmento = Poligono::envolvente_convexa(vec![(0.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+
+        assert_eq!(segmento.vertices().len(), 2);
+        assert_eq!(segmento.area(), 0.0);
+    }
+
+    #[test]
+    fn test_corte_vertical_fuera_del_poligono() {
+        let triangulo = Poligono::envolvente_convexa(vec![(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+
+        assert_eq!(triangulo.corte_vertical(-1.0), None);
+        assert_eq!(
+            triangulo.corte_vertical(1.0),
+            Some(Intervalo {
+                inicio: 0.0,
+                fin: 3.0
+            })
+        );
+    }
+}
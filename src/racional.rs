@@ -0,0 +1,317 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Número racional exacto, siempre guardado en su forma irreducible.
+///
+/// El denominador es siempre positivo y coprimo con el numerador, por lo que dos
+/// racionales iguales tienen exactamente la misma representación. Los operadores se
+/// calculan con `i128` y desbordan en los mismos casos que ese tipo; para detectar el
+/// desborde están `checked_add`, `checked_sub` y `checked_mul`. La comparación nunca
+/// desborda.
+///
+/// # Campos
+///
+/// * `numerador` - El numerador, con el signo del número
+/// * `denominador` - El denominador, siempre positivo
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::racional::Racional;
+///
+/// let un_tercio = Racional::new(1, 3).unwrap();
+/// let un_sexto = Racional::new(2, 12).unwrap();
+///
+/// assert_eq!(un_tercio + un_sexto, Racional::new(1, 2).unwrap());
+/// assert_eq!((un_tercio - un_sexto).to_string(), "1/6");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Racional {
+    numerador: i128,
+    denominador: i128,
+}
+
+impl Racional {
+    /// Crea un racional a partir de su numerador y denominador, simplificándolo.
+    ///
+    /// # Retorna
+    ///
+    /// * `Some(Racional)` - El racional en forma irreducible
+    /// * `None` - Si el denominador es cero
+    pub fn new(numerador: i128, denominador: i128) -> Option<Self> {
+        if denominador == 0 {
+            return None;
+        }
+
+        let divisor = maximo_comun_divisor(numerador, denominador) * denominador.signum();
+        Some(Self {
+            numerador: numerador / divisor,
+            denominador: denominador / divisor,
+        })
+    }
+
+    /// Crea el racional que representa a un número entero.
+    pub fn entero(valor: i128) -> Self {
+        Self {
+            numerador: valor,
+            denominador: 1,
+        }
+    }
+
+    /// Retorna el numerador de la forma irreducible.
+    pub fn numerador(&self) -> i128 {
+        self.numerador
+    }
+
+    /// Retorna el denominador de la forma irreducible, siempre positivo.
+    pub fn denominador(&self) -> i128 {
+        self.denominador
+    }
+
+    /// Retorna el `f64` más cercano al racional.
+    pub fn a_f64(&self) -> f64 {
+        self.numerador as f64 / self.denominador as f64
+    }
+
+    /// Suma dos racionales, o retorna `None` si el resultado no entra en `i128`.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::racional::Racional;
+    ///
+    /// let medio = Racional::new(1, 2).unwrap();
+    /// assert_eq!(medio.checked_add(medio), Some(Racional::entero(1)));
+    ///
+    /// let grande = Racional::new(i128::MAX, 2).unwrap();
+    /// assert_eq!(grande.checked_add(grande), None);
+    /// ```
+    pub fn checked_add(self, otro: Self) -> Option<Self> {
+        // Se usa el mínimo común múltiplo de los denominadores para achicar los productos
+        let divisor = maximo_comun_divisor(self.denominador, otro.denominador);
+        let numerador = self
+            .numerador
+            .checked_mul(otro.denominador / divisor)?
+            .checked_add(otro.numerador.checked_mul(self.denominador / divisor)?)?;
+        let denominador = (self.denominador / divisor).checked_mul(otro.denominador)?;
+        Some(Self::desde_operacion(numerador, denominador))
+    }
+
+    /// Resta dos racionales, o retorna `None` si el resultado no entra en `i128`.
+    pub fn checked_sub(self, otro: Self) -> Option<Self> {
+        let opuesto = Self {
+            numerador: otro.numerador.checked_neg()?,
+            denominador: otro.denominador,
+        };
+        self.checked_add(opuesto)
+    }
+
+    /// Multiplica dos racionales, o retorna `None` si el resultado no entra en `i128`.
+    pub fn checked_mul(self, otro: Self) -> Option<Self> {
+        // Simplificando en cruz antes de multiplicar, los productos son los del resultado
+        let cruz = maximo_comun_divisor(self.numerador, otro.denominador);
+        let otra_cruz = maximo_comun_divisor(otro.numerador, self.denominador);
+        Some(Self::desde_operacion(
+            (self.numerador / cruz).checked_mul(otro.numerador / otra_cruz)?,
+            (self.denominador / otra_cruz).checked_mul(otro.denominador / cruz)?,
+        ))
+    }
+
+    /// Arma el resultado de una operación; el denominador nunca es cero porque es
+    /// producto de denominadores positivos.
+    fn desde_operacion(numerador: i128, denominador: i128) -> Self {
+        let divisor = maximo_comun_divisor(numerador, denominador);
+        Self {
+            numerador: numerador / divisor,
+            denominador: denominador / divisor,
+        }
+    }
+}
+
+/// Calcula el máximo común divisor positivo con el algoritmo de Euclides.
+///
+/// Retorna 1 si ambos valores son cero, para poder dividir por el resultado siempre.
+fn maximo_comun_divisor(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+impl Add for Racional {
+    type Output = Self;
+
+    fn add(self, otro: Self) -> Self {
+        Self::desde_operacion(
+            self.numerador * otro.denominador + otro.numerador * self.denominador,
+            self.denominador * otro.denominador,
+        )
+    }
+}
+
+impl Sub for Racional {
+    type Output = Self;
+
+    fn sub(self, otro: Self) -> Self {
+        self + (-otro)
+    }
+}
+
+impl Mul for Racional {
+    type Output = Self;
+
+    fn mul(self, otro: Self) -> Self {
+        Self::desde_operacion(
+            self.numerador * otro.numerador,
+            self.denominador * otro.denominador,
+        )
+    }
+}
+
+impl Neg for Racional {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerador: -self.numerador,
+            denominador: self.denominador,
+        }
+    }
+}
+
+impl Ord for Racional {
+    fn cmp(&self, otro: &Self) -> Ordering {
+        // Los denominadores son positivos, así que multiplicar en cruz conserva el orden
+        match (
+            self.numerador.checked_mul(otro.denominador),
+            otro.numerador.checked_mul(self.denominador),
+        ) {
+            (Some(izquierda), Some(derecha)) => izquierda.cmp(&derecha),
+            _ => comparar_sin_desborde(
+                (self.numerador, self.denominador),
+                (otro.numerador, otro.denominador),
+            ),
+        }
+    }
+}
+
+/// Compara `a / b` con `c / d`, con `b` y `d` positivos, sin multiplicar.
+///
+/// Compara las partes enteras y, si coinciden, las inversas de las partes fraccionarias
+/// en orden contrario, como en el algoritmo de Euclides.
+fn comparar_sin_desborde((mut a, mut b): (i128, i128), (mut c, mut d): (i128, i128)) -> Ordering {
+    loop {
+        let (entero_ab, entero_cd) = (a.div_euclid(b), c.div_euclid(d));
+        if entero_ab != entero_cd {
+            return entero_ab.cmp(&entero_cd);
+        }
+
+        let (resto_ab, resto_cd) = (a.rem_euclid(b), c.rem_euclid(d));
+        match (resto_ab == 0, resto_cd == 0) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            // resto_ab / b < resto_cd / d si y solo si d / resto_cd < b / resto_ab
+            (false, false) => (a, b, c, d) = (d, resto_cd, b, resto_ab),
+        }
+    }
+}
+
+impl PartialOrd for Racional {
+    fn partial_cmp(&self, otro: &Self) -> Option<Ordering> {
+        Some(self.cmp(otro))
+    }
+}
+
+impl fmt::Display for Racional {
+    /// Muestra el racional como `numerador/denominador`, o solo el numerador si es entero.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominador == 1 {
+            write!(f, "{}", self.numerador)
+        } else {
+            write!(f, "{}/{}", self.numerador, self.denominador)
+        }
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn racional(numerador: i128, denominador: i128) -> Racional {
+        Racional::new(numerador, denominador).unwrap()
+    }
+
+    #[test]
+    fn test_forma_irreducible() {
+        let valor = racional(6, -8);
+        assert_eq!(valor.numerador(), -3);
+        assert_eq!(valor.denominador(), 4);
+        assert_eq!(racional(0, -5), Racional::entero(0));
+    }
+
+    #[test]
+    fn test_denominador_cero() {
+        assert_eq!(Racional::new(1, 0), None);
+    }
+
+    #[test]
+    fn test_operaciones() {
+        assert_eq!(racional(1, 2) + racional(1, 3), racional(5, 6));
+        assert_eq!(racional(1, 2) - racional(1, 3), racional(1, 6));
+        assert_eq!(racional(2, 3) * racional(9, 4), racional(3, 2));
+        assert_eq!(-racional(2, 3), racional(-2, 3));
+    }
+
+    #[test]
+    fn test_orden() {
+        assert!(racional(1, 3) < racional(1, 2));
+        assert!(racional(-1, 2) < racional(-1, 3));
+        assert_eq!(racional(2, 4).cmp(&racional(1, 2)), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_orden_sin_desborde() {
+        let casi_uno = racional(i64::MAX as i128 - 1, i64::MAX as i128);
+        let mil = racional(1000 * i64::MAX as i128, i64::MAX as i128 - 1);
+
+        assert!(casi_uno < Racional::entero(1));
+        assert!(mil > Racional::entero(1000) && mil < Racional::entero(1001));
+        assert!(racional(i128::MAX, 3) > racional(i128::MAX - 1, 3));
+        assert_eq!(
+            racional(-i128::MAX, 7).cmp(&racional(-i128::MAX, 7)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_operaciones_con_desborde() {
+        let grande = racional(i128::MAX, 3);
+
+        assert_eq!(grande.checked_add(grande), None);
+        assert_eq!(
+            grande.checked_mul(racional(3, 1)),
+            Some(Racional::entero(i128::MAX))
+        );
+        assert_eq!(grande.checked_mul(racional(4, 1)), None);
+        assert_eq!(
+            racional(1, 2).checked_sub(racional(1, 3)),
+            Some(racional(1, 6))
+        );
+    }
+
+    #[test]
+    fn test_mostrar() {
+        assert_eq!(racional(10, 2).to_string(), "5");
+        assert_eq!(racional(-1, 3).to_string(), "-1/3");
+        assert!((racional(1, 4).a_f64() - 0.25).abs() < 1e-15);
+    }
+}
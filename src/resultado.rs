@@ -0,0 +1,297 @@
+use crate::area_certificada::AreaCertificada;
+use crate::area_con_penumbra::AreaConPenumbra;
+use crate::busqueda_de_angulo::AnguloBuscado;
+use crate::escena::CoberturaPorFuentes;
+use crate::intervalos::Intervalo;
+use crate::racional::Racional;
+
+/// Resultado de procesar una entrada completa.
+///
+/// # Campos
+///
+/// * `area` - La longitud total cubierta por las sombras
+/// * `area_exacta` - La misma longitud como racional exacto, si el sol se dio como pendiente
+/// * `intervalos` - Los tramos de sombra fusionados, si se pidieron en las opciones
+/// * `certificado` - Las cotas rigurosas del área, si se pidieron en las opciones
+/// * `penumbra` - La umbra y la penumbra con un sol de tamaño finito, si se pidieron en las opciones
+/// * `angulo` - El ángulo del sol que produce la longitud objetivo, si se pidió en las opciones
+/// * `barrido` - La tabla de pares `(ángulo, longitud en sombra)`, si se pidió en las opciones
+/// * `fuentes` - La cobertura con el sol de la entrada y los soles extra, si se pidieron
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::resultado::Resultado;
+///
+/// let resultado = Resultado {
+///     area: 15.0,
+///     area_exacta: None,
+///     intervalos: Some(vec![Intervalo { inicio: 0.0, fin: 15.0 }]),
+///     certificado: None,
+///     penumbra: None,
+///     angulo: None,
+///     barrido: None,
+///     fuentes: None,
+/// };
+/// assert_eq!(resultado.formatear(2), "15.00\n0.00 15.00");
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Resultado {
+    pub area: f64,
+    pub area_exacta: Option<Racional>,
+    pub intervalos: Option<Vec<Intervalo>>,
+    pub certificado: Option<AreaCertificada>,
+    pub penumbra: Option<AreaConPenumbra>,
+    pub angulo: Option<AnguloBuscado>,
+    pub barrido: Option<Vec<(f64, f64)>>,
+    pub fuentes: Option<CoberturaPorFuentes>,
+}
+
+impl Resultado {
+    /// Da formato al resultado con la cantidad de decimales indicada.
+    ///
+    /// La primera línea es siempre el área. Si hay área exacta, sigue una línea con el
+    /// racional irreducible `numerador/denominador`. Si el área está certificada, sigue
+    /// una línea con su error máximo en notación científica, sin redondear. Si se pidió la penumbra,
+    /// sigue una línea con la umbra y la penumbra separadas por un espacio. Si se buscó
+    /// un ángulo, sigue una línea con el ángulo encontrado, o con `Fuera de alcance` y
+    /// las longitudes mínima y máxima que se pueden lograr. Si se pidió un barrido, sigue
+    /// una línea por ángulo con el ángulo y la longitud en sombra. Si se pidieron varios
+    /// soles, sigue una línea con la unión, la umbra y la longitud de cada sol. Si hay
+    /// intervalos, se agrega una línea por tramo con su inicio y su fin separados por un
+    /// espacio.
+    ///
+    /// # Argumentos
+    ///
+    /// * `decimales` - La cantidad de decimales con la que se imprime cada número
+    pub fn formatear(&self, decimales: usize) -> String {
+        let mut texto = format!("{:.prec$}", self.area, prec = decimales);
+
+        if let Some(exacta) = &self.area_exacta {
+            texto.push_str(&format!("\n{}", exacta));
+        }
+
+        if let Some(certificado) = &self.certificado {
+            texto.push_str(&format!("\n{:e}", certificado.error_maximo()));
+        }
+
+        if let Some(penumbra) = &self.penumbra {
+            texto.push_str(&format!(
+                "\n{:.prec$} {:.prec$}",
+                penumbra.umbra,
+                penumbra.penumbra,
+                prec = decimales
+            ));
+        }
+
+        match &self.angulo {
+            Some(AnguloBuscado::Encontrado(theta)) => {
+                texto.push_str(&format!("\n{:.prec$}", theta, prec = decimales))
+            }
+            Some(AnguloBuscado::FueraDeAlcance { minima, maxima }) => texto.push_str(&format!(
+                "\nFuera de alcance {:.prec$} {:.prec$}",
+                minima,
+                maxima,
+                prec = decimales
+            )),
+            None => {}
+        }
+
+        for (grados, longitud) in self.barrido.iter().flatten() {
+            texto.push_str(&format!(
+                "\n{:.prec$} {:.prec$}",
+                grados,
+                longitud,
+                prec = decimales
+            ));
+        }
+
+        if let Some(fuentes) = &self.fuentes {
+            texto.push('\n');
+            let valores = [fuentes.union, fuentes.umbra]
+                .into_iter()
+                .chain(fuentes.por_fuente.iter().copied());
+            let columnas: Vec<String> = valores
+                .map(|valor| format!("{:.prec$}", valor, prec = decimales))
+                .collect();
+            texto.push_str(&columnas.join(" "));
+        }
+
+        for tramo in self.intervalos.iter().flatten() {
+            texto.push_str(&format!(
+                "\n{:.prec$} {:.prec$}",
+                tramo.inicio,
+                tramo.fin,
+                prec = decimales
+            ));
+        }
+
+        texto
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatear_solo_area() {
+        let resultado = Resultado {
+            area: 446.4101615137755,
+            area_exacta: None,
+            intervalos: None,
+            certificado: None,
+            penumbra: None,
+            angulo: None,
+            barrido: None,
+            fuentes: None,
+        };
+        assert_eq!(resultado.formatear(13), "446.4101615137755");
+    }
+
+    #[test]
+    fn test_formatear_con_intervalos() {
+        let resultado = Resultado {
+            area: 12.0,
+            area_exacta: None,
+            intervalos: Some(vec![
+                Intervalo {
+                    inicio: 0.0,
+                    fin: 5.0,
+                },
+                Intervalo {
+                    inicio: 10.0,
+                    fin: 17.0,
+                },
+            ]),
+            certificado: None,
+            penumbra: None,
+            angulo: None,
+            barrido: None,
+            fuentes: None,
+        };
+        assert_eq!(resultado.formatear(1), "12.0\n0.0 5.0\n10.0 17.0");
+    }
+
+    #[test]
+    fn test_formatear_con_certificado() {
+        let resultado = Resultado {
+            area: 15.0,
+            area_exacta: None,
+            intervalos: None,
+            certificado: Some(AreaCertificada {
+                area: 15.0,
+                cota_inferior: 14.99,
+                cota_superior: 15.005,
+            }),
+            penumbra: None,
+            angulo: None,
+            barrido: None,
+            fuentes: None,
+        };
+        let texto = resultado.formatear(2);
+        let mut lineas = texto.lines();
+
+        assert_eq!(lineas.next(), Some("15.00"));
+        let error: f64 = lineas.next().unwrap().parse().unwrap();
+        assert!((error - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_formatear_con_penumbra() {
+        let resultado = Resultado {
+            area: 15.0,
+            area_exacta: None,
+            intervalos: Some(vec![Intervalo {
+                inicio: 0.0,
+                fin: 15.0,
+            }]),
+            certificado: None,
+            penumbra: Some(AreaConPenumbra {
+                umbra: 14.5,
+                penumbra: 0.75,
+            }),
+            angulo: None,
+            barrido: None,
+            fuentes: None,
+        };
+        assert_eq!(resultado.formatear(2), "15.00\n14.50 0.75\n0.00 15.00");
+    }
+
+    #[test]
+    fn test_formatear_con_angulo() {
+        let mut resultado = Resultado {
+            area: 15.0,
+            area_exacta: None,
+            intervalos: None,
+            certificado: None,
+            penumbra: None,
+            angulo: Some(AnguloBuscado::Encontrado(30.0)),
+            barrido: None,
+            fuentes: None,
+        };
+        assert_eq!(resultado.formatear(1), "15.0\n30.0");
+
+        resultado.angulo = Some(AnguloBuscado::FueraDeAlcance {
+            minima: 1.5,
+            maxima: 56.25,
+        });
+        assert_eq!(resultado.formatear(1), "15.0\nFuera de alcance 1.5 56.2");
+    }
+
+    #[test]
+    fn test_formatear_con_barrido() {
+        let resultado = Resultado {
+            area: 15.0,
+            area_exacta: None,
+            intervalos: None,
+            certificado: None,
+            penumbra: None,
+            angulo: None,
+            barrido: Some(vec![(45.0, 15.0), (90.0, 0.0)]),
+            fuentes: None,
+        };
+        assert_eq!(resultado.formatear(1), "15.0\n45.0 15.0\n90.0 0.0");
+    }
+
+    #[test]
+    fn test_formatear_con_fuentes() {
+        let resultado = Resultado {
+            area: 10.0,
+            area_exacta: None,
+            intervalos: None,
+            certificado: None,
+            penumbra: None,
+            angulo: None,
+            barrido: None,
+            fuentes: Some(CoberturaPorFuentes {
+                union: 20.0,
+                umbra: 0.0,
+                por_fuente: vec![10.0, 10.0],
+            }),
+        };
+        assert_eq!(resultado.formatear(1), "10.0\n20.0 0.0 10.0 10.0");
+    }
+
+    #[test]
+    fn test_formatear_con_area_exacta() {
+        let resultado = Resultado {
+            area: 5.5,
+            area_exacta: Some(Racional::new(11, 2).unwrap()),
+            intervalos: None,
+            certificado: None,
+            penumbra: None,
+            angulo: None,
+            barrido: None,
+            fuentes: None,
+        };
+        assert_eq!(resultado.formatear(2), "5.50\n11/2");
+    }
+}
@@ -0,0 +1,120 @@
+use crate::ErrorTipo;
+use crate::intervalos::Intervalo;
+
+/// Silueta poligonal de un flatlander, para objetos que no son postes (árboles, carpas,
+/// techos inclinados).
+///
+/// # Campos
+///
+/// * `vertices` - Los vértices `(dx, dy)` del polígono, relativos a la base del flatlander
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::silueta::Silueta;
+///
+/// // Una carpa triangular de 4 de ancho y 2 de alto
+/// let carpa = Silueta::new(vec![(0.0, 0.0), (2.0, 2.0), (4.0, 0.0)]).unwrap();
+/// assert_eq!(carpa.altura(), 2.0);
+///
+/// // Con el sol a 45° cada vértice se corre hacia el este tanto como su altura
+/// assert_eq!(carpa.proyectar(10.0, 1.0), Intervalo { inicio: 10.0, fin: 14.0 });
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Silueta {
+    vertices: Vec<(f64, f64)>,
+}
+
+impl Silueta {
+    /// Crea una silueta a partir de sus vértices.
+    ///
+    /// # Argumentos
+    ///
+    /// * `vertices` - Los vértices `(dx, dy)` del polígono, relativos a la base del flatlander
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::ArgumentoInvalido` - Si no hay vértices, alguno no es finito o alguno
+    ///   queda debajo del suelo
+    pub fn new(vertices: Vec<(f64, f64)>) -> Result<Self, ErrorTipo> {
+        let validos = vertices
+            .iter()
+            .all(|&(dx, dy)| dx.is_finite() && dy.is_finite() && dy >= 0.0);
+        if vertices.is_empty() || !validos {
+            return Err(ErrorTipo::ArgumentoInvalido);
+        }
+        Ok(Self { vertices })
+    }
+
+    /// Retorna los vértices de la silueta.
+    pub fn vertices(&self) -> &[(f64, f64)] {
+        &self.vertices
+    }
+
+    /// Retorna la altura del vértice más alto.
+    pub fn altura(&self) -> f64 {
+        self.vertices.iter().fold(0.0, |maxima, v| v.1.max(maxima))
+    }
+
+    /// Proyecta la silueta sobre el camino siguiendo los rayos del sol.
+    ///
+    /// Como el polígono es conexo su sombra también lo es, así que basta con proyectar
+    /// cada vértice y quedarse con el menor y el mayor.
+    ///
+    /// # Argumentos
+    ///
+    /// * `x` - La posición de la base del flatlander
+    /// * `corrimiento` - Cuánto se corre la sombra hacia el este por cada unidad de
+    ///   altura, negativo si cae hacia el oeste
+    pub fn proyectar(&self, x: f64, corrimiento: f64) -> Intervalo {
+        let proyecciones = self
+            .vertices
+            .iter()
+            .map(|&(dx, dy)| x + dx + dy * corrimiento);
+
+        Intervalo {
+            inicio: proyecciones.clone().fold(f64::INFINITY, f64::min),
+            fin: proyecciones.fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silueta_invalida() {
+        assert_eq!(Silueta::new(vec![]), Err(ErrorTipo::ArgumentoInvalido));
+        assert_eq!(
+            Silueta::new(vec![(0.0, 0.0), (1.0, -1.0)]),
+            Err(ErrorTipo::ArgumentoInvalido)
+        );
+        assert_eq!(
+            Silueta::new(vec![(f64::NAN, 0.0)]),
+            Err(ErrorTipo::ArgumentoInvalido)
+        );
+    }
+
+    #[test]
+    fn test_proyectar_hacia_el_oeste() {
+        // Techo inclinado: pared de 3 en dx = 0 y de 1 en dx = 6
+        let casa = Silueta::new(vec![(0.0, 0.0), (0.0, 3.0), (6.0, 1.0), (6.0, 0.0)]).unwrap();
+
+        // La pared alta, corrida 2 por unidad hacia el oeste, llega a 10 - 6
+        assert_eq!(
+            casa.proyectar(10.0, -2.0),
+            Intervalo {
+                inicio: 4.0,
+                fin: 16.0
+            }
+        );
+    }
+}
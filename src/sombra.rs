@@ -0,0 +1,137 @@
+use crate::intervalos::Intervalo;
+
+/// Sombra proyectada sobre el camino, que puede no tener fin.
+///
+/// Con una fuente de luz a distancia finita, un flatlander tan alto como la luz (o más)
+/// proyecta una sombra que se extiende sin límite hacia el lado opuesto a la fuente.
+///
+/// # Variantes
+///
+/// * `Acotada` - Sombra que cubre un intervalo finito del camino
+/// * `HaciaElEste` - Sombra que empieza en `inicio` y se extiende sin fin hacia el este
+/// * `HaciaElOeste` - Sombra que termina en `fin` y se extiende sin fin hacia el oeste
+/// * `Completa` - Sombra que cubre todo el camino, como la de un flatlander ancho con
+///   una lámpara encima más baja que él
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::sombra::Sombra;
+///
+/// let sombra = Sombra::HaciaElEste { inicio: 10.0 };
+/// assert!(!sombra.es_acotada());
+/// assert_eq!(sombra.como_intervalo(), Intervalo { inicio: 10.0, fin: f64::INFINITY });
+/// ```
+#[derive(Debug, PartialEq)]
+pub enum Sombra {
+    Acotada(Intervalo),
+    HaciaElEste { inicio: f64 },
+    HaciaElOeste { fin: f64 },
+    Completa,
+}
+
+impl Sombra {
+    /// Indica si la sombra cubre un tramo finito del camino.
+    pub fn es_acotada(&self) -> bool {
+        matches!(self, Sombra::Acotada(_))
+    }
+
+    /// Retorna la sombra como intervalo, usando extremos infinitos si no es acotada.
+    pub fn como_intervalo(&self) -> Intervalo {
+        match self {
+            Sombra::Acotada(intervalo) => *intervalo,
+            Sombra::HaciaElEste { inicio } => Intervalo {
+                inicio: *inicio,
+                fin: f64::INFINITY,
+            },
+            Sombra::HaciaElOeste { fin } => Intervalo {
+                inicio: f64::NEG_INFINITY,
+                fin: *fin,
+            },
+            Sombra::Completa => Intervalo {
+                inicio: f64::NEG_INFINITY,
+                fin: f64::INFINITY,
+            },
+        }
+    }
+
+    /// Retorna la menor sombra que cubre a las dos, que se asumen superpuestas o contiguas.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+    /// use el_rompecabezas_de_las_sombreas::sombra::Sombra;
+    ///
+    /// let oeste = Sombra::HaciaElOeste { fin: 0.0 };
+    /// let este = Sombra::Acotada(Intervalo { inicio: 0.0, fin: 5.0 });
+    /// assert_eq!(oeste.abarcar(&este), Sombra::HaciaElOeste { fin: 5.0 });
+    /// ```
+    pub fn abarcar(&self, otra: &Sombra) -> Sombra {
+        let (a, b) = (self.como_intervalo(), otra.como_intervalo());
+        Sombra::desde_extremos(a.inicio.min(b.inicio), a.fin.max(b.fin))
+    }
+
+    /// Arma la sombra que va de `inicio` a `fin`, donde un extremo infinito indica que
+    /// la sombra no tiene fin hacia ese lado.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```
+    /// use el_rompecabezas_de_las_sombreas::sombra::Sombra;
+    ///
+    /// assert_eq!(
+    ///     Sombra::desde_extremos(2.0, f64::INFINITY),
+    ///     Sombra::HaciaElEste { inicio: 2.0 }
+    /// );
+    /// ```
+    pub fn desde_extremos(inicio: f64, fin: f64) -> Sombra {
+        match (inicio.is_finite(), fin.is_finite()) {
+            (true, true) => Sombra::Acotada(Intervalo { inicio, fin }),
+            (true, false) => Sombra::HaciaElEste { inicio },
+            (false, true) => Sombra::HaciaElOeste { fin },
+            (false, false) => Sombra::Completa,
+        }
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abarcar_acotadas() {
+        let a = Sombra::Acotada(Intervalo {
+            inicio: 0.0,
+            fin: 4.0,
+        });
+        let b = Sombra::Acotada(Intervalo {
+            inicio: 2.0,
+            fin: 9.0,
+        });
+
+        assert_eq!(
+            a.abarcar(&b),
+            Sombra::Acotada(Intervalo {
+                inicio: 0.0,
+                fin: 9.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_abarcar_hacia_ambos_lados() {
+        let oeste = Sombra::HaciaElOeste { fin: 3.0 };
+        let este = Sombra::HaciaElEste { inicio: 5.0 };
+
+        assert_eq!(oeste.abarcar(&este), Sombra::Completa);
+        assert!(!Sombra::Completa.es_acotada());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::intervalos::Intervalo;
+
+/// Sombra de los flatlanders sobre un terreno con pendiente.
+///
+/// # Campos
+///
+/// * `tramos` - Los tramos en sombra, proyectados sobre la horizontal, ordenados y disjuntos
+/// * `horizontal` - La longitud en sombra medida sobre la horizontal
+/// * `superficie` - La longitud en sombra medida a lo largo de la superficie del terreno
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::intervalos::Intervalo;
+/// use el_rompecabezas_de_las_sombreas::sombra_en_terreno::SombraEnTerreno;
+///
+/// // Una rampa de 3 de ancho que sube 4: mide 5 sobre la superficie
+/// let sombra = SombraEnTerreno {
+///     tramos: vec![Intervalo { inicio: 0.0, fin: 3.0 }],
+///     horizontal: 3.0,
+///     superficie: 5.0,
+/// };
+/// assert!(sombra.superficie >= sombra.horizontal);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct SombraEnTerreno {
+    pub tramos: Vec<Intervalo>,
+    pub horizontal: f64,
+    pub superficie: f64,
+}
@@ -0,0 +1,305 @@
+use crate::ErrorTipo;
+use crate::angulo_del_sol::AnguloDelSol;
+use crate::flatlanders::Flatlander;
+use crate::intervalos::{Intervalo, fusionar_intervalos, longitud_total};
+use crate::sombra_en_terreno::SombraEnTerreno;
+
+/// Perfil del suelo como una poligonal, para caminos que suben y bajan.
+///
+/// Entre dos puntos consecutivos el suelo es un segmento recto, y antes del primer punto
+/// y después del último se extiende horizontal.
+///
+/// # Campos
+///
+/// * `puntos` - Los vértices `(x, altura)` de la poligonal, con `x` estrictamente creciente
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::angulo_del_sol::AnguloDelSol;
+/// use el_rompecabezas_de_las_sombreas::flatlanders::Flatlander;
+/// use el_rompecabezas_de_las_sombreas::terreno::Terreno;
+///
+/// // Un camino que sube con pendiente 1/2 a partir de x = 0
+/// let terreno = Terreno::new(vec![(0.0, 0.0), (100.0, 50.0)]).unwrap();
+///
+/// // La punta de la sombra cae donde 10 - t = t / 2, es decir en t = 20 / 3
+/// let theta = AnguloDelSol::Grados(45.0);
+/// let sombra = terreno.sombra_en_terreno(&[Flatlander::new(0, 10, 45)], &theta);
+/// assert!((sombra.horizontal - 20.0 / 3.0).abs() < 1e-9);
+/// assert!(sombra.superficie > sombra.horizontal);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Terreno {
+    puntos: Vec<(f64, f64)>,
+}
+
+impl Terreno {
+    /// Crea un terreno a partir de los vértices de su perfil.
+    ///
+    /// # Argumentos
+    ///
+    /// * `puntos` - Los vértices `(x, altura)` de la poligonal
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::ArgumentoInvalido` - Si no hay puntos, alguno no es finito o las `x`
+    ///   no son estrictamente crecientes
+    pub fn new(puntos: Vec<(f64, f64)>) -> Result<Self, ErrorTipo> {
+        let finitos = puntos.iter().all(|p| p.0.is_finite() && p.1.is_finite());
+        let crecientes = puntos.windows(2).all(|par| par[0].0 < par[1].0);
+        if puntos.is_empty() || !finitos || !crecientes {
+            return Err(ErrorTipo::ArgumentoInvalido);
+        }
+        Ok(Self { puntos })
+    }
+
+    /// Retorna un terreno horizontal a altura 0, como el camino del enunciado.
+    pub fn plano() -> Self {
+        Self {
+            puntos: vec![(0.0, 0.0)],
+        }
+    }
+
+    /// Retorna la altura del suelo en la posición `x`.
+    pub fn altura_en(&self, x: f64) -> f64 {
+        let posicion = self.puntos.partition_point(|p| p.0 < x);
+        match (posicion.checked_sub(1), self.puntos.get(posicion)) {
+            (Some(i), Some(&(bx, by))) => {
+                let (ax, ay) = self.puntos[i];
+                ay + (by - ay) * (x - ax) / (bx - ax)
+            }
+            (_, Some(&(_, y))) => y,
+            (_, None) => self.puntos.last().map_or(0.0, |p| p.1),
+        }
+    }
+
+    /// Calcula la sombra de los flatlanders parados sobre el terreno.
+    ///
+    /// La sombra de cada flatlander va desde su base hasta el primer punto donde el rayo
+    /// de sol que pasa por su cabeza toca el suelo. Si el rayo choca con una loma antes de
+    /// bajar al valle, la sombra termina en la loma: lo que queda detrás es sombra del
+    /// propio terreno. Cada punto del contorno del flatlander se mide desde el suelo que
+    /// tiene debajo, así que su base sigue la forma del terreno.
+    ///
+    /// # Argumentos
+    ///
+    /// * `flatlanders` - Los flatlanders, cada uno parado sobre el suelo en su posición
+    /// * `theta` - El ángulo del sol, que da la dirección y la inclinación de los rayos
+    pub fn sombra_en_terreno(
+        &self,
+        flatlanders: &[Flatlander],
+        theta: &AnguloDelSol,
+    ) -> SombraEnTerreno {
+        let reflejado = self.reflejado();
+        // Avance horizontal del rayo por unidad de altura, negativo si va hacia el oeste
+        let avance = theta.longitud_de_sombra(1.0);
+        let mut sombras: Vec<Intervalo> = flatlanders
+            .iter()
+            .map(|f| self.sombra_de(&reflejado, f, avance))
+            .collect();
+
+        let tramos = fusionar_intervalos(&mut sombras);
+        SombraEnTerreno {
+            horizontal: longitud_total(&tramos),
+            superficie: tramos
+                .iter()
+                .map(|t| self.longitud_sobre_superficie(t))
+                .sum(),
+            tramos,
+        }
+    }
+
+    /// Calcula la sombra de un flatlander, usando el terreno reflejado si cae al oeste.
+    ///
+    /// Proyecta cada punto del contorno del flatlander, que es su silueta o las esquinas
+    /// de su rectángulo, y retorna el tramo que abarca todas las proyecciones. Los puntos
+    /// apoyados en el suelo, y todos con el sol a 90°, se proyectan sobre sí mismos.
+    fn sombra_de(&self, reflejado: &Terreno, flatlander: &Flatlander, avance: f64) -> Intervalo {
+        let puntas: Vec<f64> = flatlander
+            .contorno()
+            .into_iter()
+            .map(|(x, altura)| {
+                if altura == 0.0 || avance == 0.0 {
+                    x
+                } else if avance > 0.0 {
+                    self.impacto_hacia_el_este(x, altura, 1.0 / avance)
+                } else {
+                    -reflejado.impacto_hacia_el_este(-x, altura, -1.0 / avance)
+                }
+            })
+            .collect();
+
+        Intervalo {
+            inicio: puntas.iter().copied().fold(f64::INFINITY, f64::min),
+            fin: puntas.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Busca el primer punto al este de `x` donde el suelo alcanza el rayo que sale de la
+    /// cabeza de un flatlander de altura `h` parado en `x`, bajando `caida` por unidad.
+    fn impacto_hacia_el_este(&self, x: f64, h: f64, caida: f64) -> f64 {
+        let cabeza = self.altura_en(x) + h;
+        // Diferencia entre el suelo y el rayo, negativa mientras el rayo va por encima
+        let mut anterior = (x, -h);
+
+        for &(px, py) in self.puntos.iter().filter(|p| p.0 > x) {
+            let diferencia = py - (cabeza - (px - x) * caida);
+            if diferencia >= 0.0 {
+                let (ax, ad) = anterior;
+                return ax + (px - ax) * -ad / (diferencia - ad);
+            }
+            anterior = (px, diferencia);
+        }
+
+        // Pasado el último vértice el suelo es horizontal
+        let (ultimo_x, ultima_diferencia) = anterior;
+        ultimo_x - ultima_diferencia / caida
+    }
+
+    /// Retorna el terreno reflejado respecto de `x = 0`, para tratar las sombras hacia
+    /// el oeste como si fueran hacia el este.
+    fn reflejado(&self) -> Self {
+        Self {
+            puntos: self.puntos.iter().rev().map(|&(x, y)| (-x, y)).collect(),
+        }
+    }
+
+    /// Mide a lo largo del suelo el tramo del terreno que está sobre el intervalo dado.
+    fn longitud_sobre_superficie(&self, tramo: &Intervalo) -> f64 {
+        let mut longitud = 0.0;
+        let mut anterior = (tramo.inicio, self.altura_en(tramo.inicio));
+
+        let interiores = self
+            .puntos
+            .iter()
+            .filter(|p| p.0 > tramo.inicio && p.0 < tramo.fin)
+            .copied();
+        for (x, y) in interiores.chain([(tramo.fin, self.altura_en(tramo.fin))]) {
+            longitud += (x - anterior.0).hypot(y - anterior.1);
+            anterior = (x, y);
+        }
+
+        longitud
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PRECISION_IGUALDAD_FLOAT;
+    use crate::intervalos::calcular_area;
+    use crate::silueta::Silueta;
+
+    #[test]
+    fn test_terreno_invalido() {
+        assert_eq!(Terreno::new(vec![]), Err(ErrorTipo::ArgumentoInvalido));
+        assert_eq!(
+            Terreno::new(vec![(0.0, 0.0), (0.0, 1.0)]),
+            Err(ErrorTipo::ArgumentoInvalido)
+        );
+        assert_eq!(
+            Terreno::new(vec![(0.0, f64::NAN)]),
+            Err(ErrorTipo::ArgumentoInvalido)
+        );
+    }
+
+    #[test]
+    fn test_altura_en() {
+        let terreno = Terreno::new(vec![(0.0, 0.0), (10.0, 5.0), (20.0, 1.0)]).unwrap();
+
+        assert_eq!(terreno.altura_en(-5.0), 0.0);
+        assert_eq!(terreno.altura_en(4.0), 2.0);
+        assert_eq!(terreno.altura_en(10.0), 5.0);
+        assert!((terreno.altura_en(15.0) - 3.0).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert_eq!(terreno.altura_en(30.0), 1.0);
+    }
+
+    #[test]
+    fn test_plano_coincide_con_calcular_area() {
+        let carpa = || Silueta::new(vec![(0.0, 0.0), (2.0, 4.0), (4.0, 0.0)]).unwrap();
+        for theta in [30, 135] {
+            let angulo = AnguloDelSol::Grados(theta as f64);
+            let flatlanders = vec![
+                Flatlander::new(50, 150, theta),
+                Flatlander::new(0, 100, theta),
+                Flatlander::new(100, 200, theta),
+                Flatlander::new(3000, 10, theta).con_ancho(7),
+                Flatlander::new(5000, 10, theta).con_elevacion(4).unwrap(),
+                Flatlander::new(6000, 10, theta)
+                    .con_ancho(3)
+                    .con_elevacion(4)
+                    .unwrap(),
+                Flatlander::con_silueta(7000, carpa(), &angulo),
+            ];
+            let mut sombras: Vec<Intervalo> = flatlanders.iter().map(Flatlander::sombra).collect();
+            let area = calcular_area(&mut sombras);
+
+            let sombra = Terreno::plano().sombra_en_terreno(&flatlanders, &angulo);
+            assert!((sombra.horizontal - area).abs() < 1e-6);
+            assert!((sombra.superficie - area).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_silueta_sobre_subida() {
+        // Poste de 10 de alto como silueta, sobre la subida de pendiente 1/2 del ejemplo
+        let terreno = Terreno::new(vec![(0.0, 0.0), (100.0, 50.0)]).unwrap();
+        let theta = AnguloDelSol::Grados(45.0);
+        let poste = Silueta::new(vec![(0.0, 0.0), (0.0, 10.0)]).unwrap();
+
+        let sombra =
+            terreno.sombra_en_terreno(&[Flatlander::con_silueta(0, poste, &theta)], &theta);
+        assert!((sombra.horizontal - 20.0 / 3.0).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_angulo_distinto_al_de_creacion() {
+        // La sombra usa el ángulo pedido, no la longitud calculada al crear el flatlander
+        let flatlander = Flatlander::new(0, 10, 30);
+        let sombra = Terreno::plano().sombra_en_terreno(&[flatlander], &AnguloDelSol::Grados(45.0));
+
+        assert!((sombra.horizontal - 10.0).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_flatlander_en_el_extremo_del_camino() {
+        // x + w no entra en u32, pero se calcula en f64
+        let ancho = Flatlander::new(u32::MAX, 10, 90).con_ancho(u32::MAX);
+        let sombra = Terreno::plano().sombra_en_terreno(&[ancho], &AnguloDelSol::Grados(90.0));
+
+        assert_eq!(sombra.tramos[0].fin, 2.0 * u32::MAX as f64);
+    }
+
+    #[test]
+    fn test_rayo_choca_con_una_loma() {
+        // Loma de altura 9 en x = 5, y después un valle profundo
+        let terreno = Terreno::new(vec![(0.0, 0.0), (4.0, 0.0), (5.0, 9.0), (6.0, -20.0)]).unwrap();
+        let theta = AnguloDelSol::Grados(45.0);
+        let sombra = terreno.sombra_en_terreno(&[Flatlander::new(0, 10, 45)], &theta);
+
+        // El rayo baja de 10 a 6 hasta x = 4 y toca la ladera (pendiente 9) en 4 + 6 / 10,
+        // sin llegar al valle que queda detrás de la loma
+        assert!((sombra.horizontal - 4.6).abs() < PRECISION_IGUALDAD_FLOAT);
+        let ladera = 0.6_f64.hypot(5.4);
+        assert!((sombra.superficie - (4.0 + ladera)).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+
+    #[test]
+    fn test_sombra_hacia_el_oeste_sobre_bajada() {
+        // El terreno baja hacia el oeste con pendiente 1/2, y más allá de x = -20 es plano
+        let terreno = Terreno::new(vec![(-20.0, -10.0), (0.0, 0.0)]).unwrap();
+        let theta = AnguloDelSol::Grados(135.0);
+        let sombra = terreno.sombra_en_terreno(&[Flatlander::new(0, 10, 135)], &theta);
+
+        // El rayo baja 1 por unidad y el suelo 1/2: se encuentran en x = -20, altura -10
+        assert!((sombra.tramos[0].inicio + 20.0).abs() < PRECISION_IGUALDAD_FLOAT);
+        assert!((sombra.superficie - 20.0_f64.hypot(10.0)).abs() < PRECISION_IGUALDAD_FLOAT);
+    }
+}
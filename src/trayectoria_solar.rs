@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+
+use crate::ErrorTipo;
+
+/// Recorrido del sol a lo largo del día, dividido en pasos de tiempo iguales.
+///
+/// En cada paso el sol se considera fijo en el ángulo que tiene a la mitad del paso. El
+/// ángulo se mide desde el horizonte oeste, así que el sol sale por el este con ángulos
+/// cercanos a 180° y se pone por el oeste con ángulos cercanos a 0°.
+///
+/// # Campos
+///
+/// * `duracion_del_paso` - Las horas que dura cada paso
+/// * `thetas` - El ángulo del sol en cada paso, en grados entre 0 y 180 sin incluirlos
+///
+/// # Ejemplo
+///
+/// ```
+/// use el_rompecabezas_de_las_sombreas::trayectoria_solar::TrayectoriaSolar;
+///
+/// let trayectoria = TrayectoriaSolar::arco_diario(6.0, 18.0, 4).unwrap();
+///
+/// assert_eq!(trayectoria.duracion_del_paso(), 3.0);
+/// assert_eq!(trayectoria.thetas(), &[157.5, 112.5, 67.5, 22.5]);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct TrayectoriaSolar {
+    duracion_del_paso: f64,
+    thetas: Vec<f64>,
+}
+
+impl TrayectoriaSolar {
+    /// Arma la trayectoria evaluando un perfil de elevación en la mitad de cada paso.
+    ///
+    /// # Argumentos
+    ///
+    /// * `amanecer` - La hora en que sale el sol
+    /// * `atardecer` - La hora en que se pone el sol
+    /// * `pasos` - La cantidad de pasos de tiempo en que se divide el día
+    /// * `perfil` - El ángulo del sol, en grados desde el horizonte oeste, para cada hora
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::FueraDeRango` - Si no hay pasos, el atardecer no es posterior al
+    ///   amanecer, o el perfil da un ángulo fuera de `(0, 180)`
+    pub fn desde_perfil<F: Fn(f64) -> f64>(
+        amanecer: f64,
+        atardecer: f64,
+        pasos: u32,
+        perfil: F,
+    ) -> Result<Self, ErrorTipo> {
+        if pasos == 0 || amanecer.partial_cmp(&atardecer) != Some(Ordering::Less) {
+            return Err(ErrorTipo::FueraDeRango);
+        }
+
+        let duracion_del_paso = (atardecer - amanecer) / pasos as f64;
+        let thetas = (0..pasos)
+            .map(|paso| perfil(amanecer + (paso as f64 + 0.5) * duracion_del_paso))
+            .map(|theta| {
+                if theta > 0.0 && theta < 180.0 {
+                    Ok(theta)
+                } else {
+                    Err(ErrorTipo::FueraDeRango)
+                }
+            })
+            .collect::<Result<Vec<f64>, ErrorTipo>>()?;
+
+        Ok(Self {
+            duracion_del_paso,
+            thetas,
+        })
+    }
+
+    /// Arma la trayectoria de un sol que recorre el cielo a velocidad constante, desde
+    /// el horizonte este al amanecer hasta el horizonte oeste al atardecer.
+    ///
+    /// # Argumentos
+    ///
+    /// * `amanecer` - La hora en que sale el sol
+    /// * `atardecer` - La hora en que se pone el sol
+    /// * `pasos` - La cantidad de pasos de tiempo en que se divide el día
+    ///
+    /// # Errores
+    ///
+    /// * `ErrorTipo::FueraDeRango` - Si no hay pasos o el atardecer no es posterior al amanecer
+    pub fn arco_diario(amanecer: f64, atardecer: f64, pasos: u32) -> Result<Self, ErrorTipo> {
+        Self::desde_perfil(amanecer, atardecer, pasos, |hora| {
+            let avance = (hora - amanecer) / (atardecer - amanecer);
+            180.0 * (1.0 - avance)
+        })
+    }
+
+    /// Retorna las horas que dura cada paso.
+    pub fn duracion_del_paso(&self) -> f64 {
+        self.duracion_del_paso
+    }
+
+    /// Retorna el ángulo del sol en cada paso.
+    pub fn thetas(&self) -> &[f64] {
+        &self.thetas
+    }
+}
+
+//
+//
+// TESTS UNITARIOS
+//
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perfil_constante() {
+        let trayectoria = TrayectoriaSolar::desde_perfil(0.0, 2.0, 8, |_| 45.5).unwrap();
+
+        assert_eq!(trayectoria.duracion_del_paso(), 0.25);
+        assert_eq!(trayectoria.thetas(), &[45.5; 8]);
+    }
+
+    #[test]
+    fn test_perfil_fuera_de_rango() {
+        assert_eq!(
+            TrayectoriaSolar::desde_perfil(0.0, 1.0, 2, |hora| if hora < 0.5 {
+                90.0
+            } else {
+                180.0
+            }),
+            Err(ErrorTipo::FueraDeRango)
+        );
+        assert_eq!(
+            TrayectoriaSolar::desde_perfil(0.0, 1.0, 2, |_| f64::NAN),
+            Err(ErrorTipo::FueraDeRango)
+        );
+    }
+
+    #[test]
+    fn test_argumentos_invalidos() {
+        assert_eq!(
+            TrayectoriaSolar::arco_diario(6.0, 18.0, 0),
+            Err(ErrorTipo::FueraDeRango)
+        );
+        assert_eq!(
+            TrayectoriaSolar::arco_diario(18.0, 6.0, 4),
+            Err(ErrorTipo::FueraDeRango)
+        );
+        assert_eq!(
+            TrayectoriaSolar::arco_diario(f64::NAN, 6.0, 4),
+            Err(ErrorTipo::FueraDeRango)
+        );
+    }
+}
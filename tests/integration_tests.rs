@@ -1,4 +1,8 @@
-use el_rompecabezas_de_las_sombreas::{ErrorTipo, procesar_entrada};
+use el_rompecabezas_de_las_sombreas::limites::Limites;
+use el_rompecabezas_de_las_sombreas::opciones::Opciones;
+use el_rompecabezas_de_las_sombreas::{
+    ErrorTipo, procesar_casos_con_opciones, procesar_entrada, procesar_entrada_con_opciones,
+};
 use std::io::Cursor;
 
 #[test]
@@ -86,3 +90,265 @@ fn test_entrada_invalida_faltan_lineas() {
         _ => panic!("Se esperaba error LineaFaltante"),
     }
 }
+
+#[test]
+fn test_intervalos_fusionados_con_opciones() {
+    let input = "45 3\n20 5\n0 10\n5 10\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones {
+        mostrar_intervalos: true,
+        ..Opciones::default()
+    };
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    assert!((resultado.area - 20.0).abs() < 0.001);
+
+    let intervalos = resultado.intervalos.unwrap();
+    assert_eq!(intervalos.len(), 2);
+    assert!((intervalos[0].inicio - 0.0).abs() < 0.001);
+    assert!((intervalos[0].fin - 15.0).abs() < 0.001);
+    assert!((intervalos[1].inicio - 20.0).abs() < 0.001);
+    assert!((intervalos[1].fin - 25.0).abs() < 0.001);
+}
+
+#[test]
+fn test_sin_intervalos_por_defecto() {
+    let input = "45 1\n10 20\n";
+    let reader = Cursor::new(input);
+
+    let resultado = procesar_entrada_con_opciones(reader, &Opciones::default()).unwrap();
+    assert!(resultado.intervalos.is_none());
+}
+
+#[test]
+fn test_area_certificada_ejemplo_enunciado() {
+    let input = "45 3\n50 150\n0 100\n100 200\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones {
+        certificado: true,
+        ..Opciones::default()
+    };
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    let certificado = resultado.certificado.unwrap();
+
+    assert!(certificado.cota_inferior <= 300.0 && 300.0 <= certificado.cota_superior);
+    assert!(certificado.es_confiable(1e-4));
+    assert_eq!(resultado.area, certificado.area);
+}
+
+#[test]
+fn test_cielo_completo_sombras_hacia_el_oeste() {
+    // Simétrico al ejemplo de 45°: el sol a 135° proyecta las sombras hacia el oeste
+    let input = "135 3\n50 150\n0 100\n100 200\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones {
+        mostrar_intervalos: true,
+        limites: Limites::cielo_completo(),
+        ..Opciones::default()
+    };
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    assert!((resultado.area - 200.0).abs() < 0.001);
+
+    let intervalos = resultado.intervalos.unwrap();
+    assert!((intervalos[0].inicio + 100.0).abs() < 0.001);
+    assert!((intervalos[0].fin - 100.0).abs() < 0.001);
+}
+
+#[test]
+fn test_limites_del_concurso_por_defecto() {
+    let input = "135 1\n10 20\n";
+    let reader = Cursor::new(input);
+
+    let resultado = procesar_entrada(reader);
+    assert_eq!(resultado, Err(ErrorTipo::FueraDeRango));
+}
+
+#[test]
+fn test_flatlanders_con_ancho() {
+    // Casa de ancho 10 en x=0 con sombra de 10, y un poste de dos columnas en x=30
+    let input = "45 2\n0 10 10\n30 5\n";
+    let reader = Cursor::new(input);
+
+    let resultado = procesar_entrada(reader).unwrap();
+    assert!((resultado - 25.0).abs() < 0.001);
+}
+
+#[test]
+fn test_flatlanders_flotantes_dejan_huecos() {
+    // Un globo entre las alturas 20 y 30 en x=0: sombra de [20, 30], separada del poste
+    let input = "45 2\n0 30 0 20\n0 10\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones {
+        mostrar_intervalos: true,
+        ..Opciones::default()
+    };
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    assert!((resultado.area - 20.0).abs() < 0.001);
+
+    let intervalos = resultado.intervalos.unwrap();
+    assert_eq!(intervalos.len(), 2);
+    assert!((intervalos[0].fin - 10.0).abs() < 0.001);
+    assert!((intervalos[1].inicio - 20.0).abs() < 0.001);
+}
+
+#[test]
+fn test_umbra_y_penumbra_con_opciones() {
+    let input = "45 2\n0 10\n5 10\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones::desde_argumentos(vec!["--penumbra".to_string()]).unwrap();
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    assert!((resultado.area - 15.0).abs() < 0.001);
+
+    let penumbra = resultado.penumbra.unwrap();
+    assert!(penumbra.umbra < resultado.area && resultado.area < penumbra.total());
+}
+
+#[test]
+fn test_varios_soles_con_opciones() {
+    // Con 45° y 135° las sombras son [0, 10] y [-10, 0], que solo se tocan en x = 0
+    let input = "45 1\n0 10\n";
+    let reader = Cursor::new(input);
+    let argumentos = vec!["--cielo-completo".to_string(), "--soles=135".to_string()];
+    let opciones = Opciones::desde_argumentos(argumentos).unwrap();
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    assert_eq!(resultado.formatear(1), "10.0\n20.0 0.0 10.0 10.0");
+
+    let reader = Cursor::new(input);
+    let opciones = Opciones::desde_argumentos(vec!["--soles=100".to_string()]).unwrap();
+    let resultado = procesar_entrada_con_opciones(reader, &opciones);
+    assert_eq!(resultado, Err(ErrorTipo::FueraDeRango));
+}
+
+#[test]
+fn test_cajas_en_tres_dimensiones() {
+    // Sol al oeste a 45°: dos cajas de 10x10 con sombras de [0, 20] y [10, 30] de largo 10
+    let input = "180 45 2\n0 0 10 10 10\n10 5 10 10 10\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones::desde_argumentos(vec!["--espacial".to_string()]).unwrap();
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    // 200 + 200 menos el rectángulo común [10, 20] x [5, 10]
+    assert!((resultado.area - 350.0).abs() < 0.001);
+    assert_eq!(resultado.formatear(2), "350.00");
+}
+
+#[test]
+fn test_cajas_con_linea_incompleta() {
+    let input = "180 45 1\n0 0 10 10\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones::desde_argumentos(vec!["--espacial".to_string()]).unwrap();
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones);
+    assert_eq!(resultado, Err(ErrorTipo::ValorFaltante));
+}
+
+#[test]
+fn test_angulo_para_longitud_objetivo() {
+    // El ejemplo del enunciado cubre 446.41 a 30°, así que con cualquier theta de entrada
+    // la búsqueda tiene que volver a 30°
+    let input = "45 3\n50 150\n0 100\n100 200\n";
+    let reader = Cursor::new(input);
+    let opciones =
+        Opciones::desde_argumentos(vec!["--objetivo=446.4101615137755".to_string()]).unwrap();
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    assert!((resultado.area - 300.0).abs() < 0.001);
+    assert_eq!(resultado.formatear(3), "300.000\n30.000");
+}
+
+#[test]
+fn test_barrido_de_angulos_del_concurso() {
+    let input = "45 3\n50 150\n0 100\n100 200\n";
+    let reader = Cursor::new(input);
+    let opciones = Opciones::desde_argumentos(vec!["--barrido".to_string()]).unwrap();
+
+    let resultado = procesar_entrada_con_opciones(reader, &opciones).unwrap();
+    let barrido = resultado.barrido.unwrap();
+    assert_eq!(barrido.len(), 71);
+
+    // La tabla coincide con correr el programa una vez por ángulo
+    for i in [0, 20, 35] {
+        let (grados, longitud) = barrido[i];
+        let input = format!("{} 3\n50 150\n0 100\n100 200\n", grados);
+        let area = procesar_entrada(Cursor::new(input)).unwrap();
+        assert!((longitud - area).abs() < 0.001);
+    }
+}
+
+#[test]
+fn test_angulo_en_otras_notaciones() {
+    // El ejemplo del enunciado a 30° escrito en grados decimales, sexagesimales y radianes
+    for theta in ["30.0", "30°0'0\"", "0.5235987755982988rad"] {
+        let input = format!("{} 3\n50 150\n0 100\n100 200\n", theta);
+        let resultado = procesar_entrada(Cursor::new(input)).unwrap();
+        assert!((resultado - 446.4101615137755).abs() < 0.001);
+    }
+}
+
+#[test]
+fn test_area_exacta_con_pendiente() {
+    // Pendiente 3/4: cada sombra mide 4/3 de la altura
+    let input = "3/4 3\n50 150\n0 100\n100 200\n";
+    let reader = Cursor::new(input);
+
+    let resultado = procesar_entrada_con_opciones(reader, &Opciones::default()).unwrap();
+    // [0, 400/3] y [100, 100 + 800/3] se superponen: la unión va de 0 a 1100/3
+    assert_eq!(resultado.formatear(3), "366.667\n1100/3");
+}
+
+#[test]
+fn test_varios_casos_con_cantidad_inicial() {
+    // El segundo caso tiene theta fuera de rango y no impide calcular el tercero
+    let input = "3\n45 3\n50 150\n0 100\n100 200\n\n95 1\n0 10\n30 3\n50 150\n0 100\n100 200\n";
+    let reader = Cursor::new(input);
+
+    let casos = procesar_casos_con_opciones(reader, &Opciones::default()).unwrap();
+    let areas: Vec<_> = casos
+        .iter()
+        .map(|caso| caso.as_ref().map(|resultado| resultado.formatear(2)))
+        .collect();
+    assert_eq!(
+        areas,
+        vec![
+            Ok("300.00".to_string()),
+            Err(&ErrorTipo::FueraDeRango),
+            Ok("446.41".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_varios_casos_hasta_el_final() {
+    // El primer caso tiene una línea con menos valores y el último está incompleto
+    let input = "45 2\n0\n5 15\n45 1\n0 10\n45 2\n0 10\n";
+    let reader = Cursor::new(input);
+
+    let casos = procesar_casos_con_opciones(reader, &Opciones::default()).unwrap();
+    assert_eq!(casos.len(), 3);
+    assert_eq!(casos[0], Err(ErrorTipo::ValorFaltante));
+    assert!(
+        casos[1]
+            .as_ref()
+            .is_ok_and(|r| (r.area - 10.0).abs() < 1e-9)
+    );
+    assert_eq!(casos[2], Err(ErrorTipo::LineaFaltante));
+}
+
+#[test]
+fn test_area_exacta_con_pendiente_de_terminos_grandes() {
+    // Pendiente apenas menor a 1 con términos cercanos a i64::MAX: los productos en cruz
+    // no entran en i128, pero el área exacta se calcula sin desbordar
+    let input = "9223372036854775806/9223372036854775807 2\n0 1000\n5000 1000\n";
+    let reader = Cursor::new(input);
+
+    let resultado = procesar_entrada_con_opciones(reader, &Opciones::default()).unwrap();
+    assert_eq!(
+        resultado.formatear(3),
+        "2000.000\n9223372036854775807000/4611686018427387903"
+    );
+}
@@ -12,7 +12,7 @@ fn test_parametros_validos() {
 
     match Parametros::leer_desde_stdin(&mut lines) {
         Ok(params) => {
-            assert_eq!(params.theta, 45);
+            assert_eq!(params.theta.grados(), 45.0);
             assert_eq!(params.n, 3);
         }
         Err(_) => panic!("Se esperaba un resultado válido"),